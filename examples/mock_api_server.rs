@@ -0,0 +1,22 @@
+use anyhow::Result;
+
+use switchbot_cli_tool::infrastructure::server::{MockApiServer, MockFixtures};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let fixtures = match args.next() {
+        Some(path) => MockFixtures::load(&path)?,
+        None => MockFixtures::default(),
+    };
+    let addr = args.next().unwrap_or_else(|| "127.0.0.1:8081".to_string());
+
+    dotenvy::dotenv().ok();
+    let token = std::env::var("SWITCHBOT_TOKEN")?;
+    let secret = std::env::var("SWITCHBOT_SECRET")?;
+
+    let server = MockApiServer::bind(&addr, &token, &secret, fixtures).await?;
+    println!("Mock SwitchBot API listening on http://{}", server.local_addr()?);
+
+    server.run().await
+}
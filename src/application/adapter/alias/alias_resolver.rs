@@ -14,4 +14,16 @@ impl AliasResolver {
     pub fn resolve<'a>(&'a self, input: &'a str) -> &'a str {
         self.aliases.get(input).map(|s| s.as_str()).unwrap_or_else(|| input)
     }
+
+    pub fn aliases_for(&self, id: &str) -> Vec<String> {
+        let mut aliases: Vec<String> = self
+            .aliases
+            .iter()
+            .filter(|(_, v)| v.as_str() == id)
+            .map(|(k, _)| k.clone())
+            .collect();
+
+        aliases.sort();
+        aliases
+    }
 }
\ No newline at end of file
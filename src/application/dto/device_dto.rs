@@ -1,33 +1,112 @@
-use anyhow::Result;
+use std::time::Duration;
 
-use crate::domain::models::entities::Device;
-use crate::domain::models::value_objects::Command;
+use anyhow::{Error, Result};
+use serde::Serialize;
+
+use crate::domain::models::entities::{Device, DeviceStatus};
+use crate::domain::models::value_objects::{BotMode, Command, DoorState, LockState, PowerState};
 
 #[derive(Debug)]
 pub struct ExecuteCommandDto {
     pub device_id: String,
     pub command: Command,
+    pub wait: bool,
+    pub repeat: u32,
+    pub interval: Duration,
+    pub offline: bool,
 }
 
 impl ExecuteCommandDto {
     pub fn new(device_id: String, command: Command) -> Self {
-        Self { device_id, command }
+        Self {
+            device_id,
+            command,
+            wait: false,
+            repeat: 1,
+            interval: Duration::ZERO,
+            offline: false,
+        }
+    }
+
+    pub fn with_wait(self, wait: bool) -> Self {
+        Self { wait, ..self }
+    }
+
+    pub fn with_repeat(self, repeat: u32, interval: Duration) -> Self {
+        Self { repeat, interval, ..self }
+    }
+
+    pub fn with_offline(self, offline: bool) -> Self {
+        Self { offline, ..self }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DeviceResponseDto {
-    pub device_id: Result<String>,
+    pub device_id: String,
     pub device_name: String,
     pub device_type: String,
+    pub is_infrared: bool,
+    pub hub_device_id: String,
+    pub aliases: Vec<String>,
 }
 
-impl From<Device> for DeviceResponseDto {
-    fn from(device: Device) -> Self {
-        Self {
-            device_id: device.id.value(),
+impl DeviceResponseDto {
+    pub fn with_aliases(self, aliases: Vec<String>) -> Self {
+        Self { aliases, ..self }
+    }
+}
+
+impl TryFrom<Device> for DeviceResponseDto {
+    type Error = Error;
+
+    fn try_from(device: Device) -> Result<Self> {
+        Ok(Self {
+            device_id: device.id.value()?,
             device_name: device.name,
-            device_type: device.device_type,
-        }
+            device_type: device.device_type.to_string(),
+            is_infrared: device.is_infrared,
+            hub_device_id: device.hub_device_id,
+            aliases: Vec::new(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeviceStatusResponseDto {
+    pub device_id: String,
+    pub device_type: String,
+    pub power: Option<PowerState>,
+    pub brightness: Option<u8>,
+    pub color: Option<(u8, u8, u8)>,
+    pub color_temperature: Option<u16>,
+    pub temperature: Option<f32>,
+    pub humidity: Option<u8>,
+    pub battery: Option<u8>,
+    pub slide_position: Option<u8>,
+    pub lock_state: Option<LockState>,
+    pub door_state: Option<DoorState>,
+    pub bot_mode: Option<BotMode>,
+}
+
+impl TryFrom<DeviceStatus> for DeviceStatusResponseDto {
+    type Error = Error;
+
+    fn try_from(status: DeviceStatus) -> Result<Self> {
+        Ok(Self {
+            device_id: status.id.value()?,
+            device_type: status.device_type.to_string(),
+            power: status.power,
+            brightness: status.brightness,
+            color: status.color,
+            color_temperature: status.color_temperature,
+            temperature: status.temperature,
+            humidity: status.humidity,
+            battery: status.battery,
+            slide_position: status.slide_position,
+            lock_state: status.lock_state,
+            door_state: status.door_state,
+            bot_mode: status.bot_mode,
+        })
     }
 }
@@ -0,0 +1,22 @@
+use serde::Serialize;
+
+use crate::domain::models::entities::ApiQuota;
+
+#[derive(Debug, Serialize)]
+pub struct QuotaResponseDto {
+    pub date: String,
+    pub used: u32,
+    pub limit: u32,
+    pub remaining: u32,
+}
+
+impl From<ApiQuota> for QuotaResponseDto {
+    fn from(quota: ApiQuota) -> Self {
+        Self {
+            date: quota.date.to_string(),
+            used: quota.used,
+            limit: quota.limit,
+            remaining: quota.remaining(),
+        }
+    }
+}
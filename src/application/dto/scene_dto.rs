@@ -0,0 +1,37 @@
+use anyhow::{Error, Result};
+use serde::Serialize;
+
+use crate::domain::models::entities::Scene;
+
+#[derive(Debug)]
+pub struct ExecuteSceneDto {
+    pub scene: String,
+    pub offline: bool,
+}
+
+impl ExecuteSceneDto {
+    pub fn new(scene: String) -> Self {
+        Self { scene, offline: false }
+    }
+
+    pub fn with_offline(self, offline: bool) -> Self {
+        Self { offline, ..self }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SceneResponseDto {
+    pub scene_id: String,
+    pub scene_name: String,
+}
+
+impl TryFrom<Scene> for SceneResponseDto {
+    type Error = Error;
+
+    fn try_from(scene: Scene) -> Result<Self> {
+        Ok(Self {
+            scene_id: scene.id.value()?,
+            scene_name: scene.name,
+        })
+    }
+}
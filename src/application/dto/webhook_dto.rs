@@ -0,0 +1,24 @@
+use serde::Serialize;
+
+use crate::domain::models::entities::Webhook;
+
+#[derive(Debug, Serialize)]
+pub struct WebhookResponseDto {
+    pub url: String,
+    pub device_list: String,
+    pub enable: bool,
+    pub create_time: Option<String>,
+    pub last_update_time: Option<String>,
+}
+
+impl From<Webhook> for WebhookResponseDto {
+    fn from(webhook: Webhook) -> Self {
+        Self {
+            url: webhook.url,
+            device_list: webhook.device_list,
+            enable: webhook.enable,
+            create_time: webhook.create_time.map(|v| v.to_rfc3339()),
+            last_update_time: webhook.last_update_time.map(|v| v.to_rfc3339()),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::application::dto::QuotaResponseDto;
+use crate::domain::repositories::IQuotaRepository;
+
+#[async_trait]
+pub trait ICheckQuotaUseCase {
+    async fn fetch_quota(&self) -> Result<QuotaResponseDto>;
+}
+
+#[derive(Debug)]
+pub struct CheckQuotaUseCase<'a, R: IQuotaRepository> {
+    repo: &'a R,
+}
+
+impl<'a, R: IQuotaRepository> CheckQuotaUseCase<'a, R> {
+    pub fn new(repo: &'a R) -> Self {
+        Self { repo }
+    }
+}
+
+#[async_trait]
+impl<R> ICheckQuotaUseCase for CheckQuotaUseCase<'_, R>
+    where
+        R: IQuotaRepository + Sync + Send,
+{
+    async fn fetch_quota(&self) -> Result<QuotaResponseDto> {
+        let quota = self.repo.get_quota().await?;
+        tracing::debug!("{:?}", quota);
+
+        Ok(quota.into())
+    }
+}
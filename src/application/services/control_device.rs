@@ -1,25 +1,107 @@
-use anyhow::Result;
+use std::time::Duration;
+
+use anyhow::{Result, bail};
 use async_trait::async_trait;
 
-use crate::application::dto::{DeviceResponseDto, ExecuteCommandDto};
+use crate::application::dto::{DeviceResponseDto, DeviceStatusResponseDto, ExecuteCommandDto};
 use crate::application::services::export_devices::export_devices_to_file;
-use crate::domain::models::value_objects::DeviceId;
+use crate::application::services::import_devices::import_devices_from_file;
+use crate::domain::errors::{ApiError, ValidationError};
+use crate::domain::models::capabilities::{validate_bot_mode, validate_command};
+use crate::domain::models::entities::Device;
+use crate::domain::models::value_objects::{Command, CommandKind, DeviceId, DeviceType, LockState};
 use crate::domain::repositories::IDeviceRepository;
 
+const DEVICE_CACHE_PATH: &str = "output/devices.json";
+const WAIT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const WAIT_MAX_ATTEMPTS: u32 = 15;
+
+// Lookups made only to validate a command must not stop the command itself from being sent
+fn can_skip_lookup(err: &anyhow::Error) -> bool {
+    matches!(err.downcast_ref(), Some(ApiError::BudgetExhausted { .. }))
+}
+
 #[async_trait]
 pub trait IControlDeviceUseCase {
     async fn execute(&self, dto: ExecuteCommandDto) -> Result<()>;
     async fn fetch_devices(&self) -> Result<Vec<DeviceResponseDto>>;
+    async fn fetch_status(&self, device_id: String) -> Result<DeviceStatusResponseDto>;
 }
 
 #[derive(Debug)]
 pub struct ControlDeviceUseCase<'a, R: IDeviceRepository> {
     repo: &'a R,
+    cache_path: Option<&'a str>,
 }
 
 impl<'a, R: IDeviceRepository> ControlDeviceUseCase<'a, R> {
     pub fn new(repo: &'a R) -> Self {
-        Self { repo }
+        Self {
+            repo,
+            cache_path: Some(DEVICE_CACHE_PATH),
+        }
+    }
+
+    pub fn with_cache_path(self, cache_path: Option<&'a str>) -> Self {
+        Self { cache_path, ..self }
+    }
+
+    async fn find_device(&self, device_id: &DeviceId, offline: bool) -> Result<Option<Device>> {
+        let cached = self
+            .cache_path
+            .and_then(|path| import_devices_from_file(path).ok())
+            .and_then(|devices| devices.into_iter().find(|v| &v.id == device_id));
+
+        match cached {
+            Some(device) => Ok(Some(device)),
+            None if offline => Ok(None),
+            None => self.repo.get_device(device_id).await.map(Some),
+        }
+    }
+
+    async fn validate_device(&self, device: &Device, command: &Command, offline: bool) -> Result<()> {
+        validate_command(&device.device_type, command.kind())?;
+
+        if command.kind().is_infrared_only() && !device.is_infrared {
+            return Err(ValidationError::new(format!(
+                "Command `{}` can only be sent to an infrared remote", command.kind()
+            )).into());
+        }
+
+        if !offline
+            && device.device_type == DeviceType::Bot
+            && matches!(command.kind(), CommandKind::TurnOn | CommandKind::TurnOff)
+        {
+            let status = match self.repo.get_device_status(&device.id).await {
+                Err(e) if can_skip_lookup(&e) => {
+                    tracing::warn!("{e}: skipping Bot mode check for {:?}", device.id);
+                    return Ok(());
+                }
+                result => result?,
+            };
+            if let Some(mode) = status.bot_mode {
+                validate_bot_mode(mode, command.kind())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn wait_for_lock_state(&self, device_id: &DeviceId, expected: LockState) -> Result<()> {
+        for attempt in 1..=WAIT_MAX_ATTEMPTS {
+            tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+
+            let status = self.repo.get_device_status(device_id).await?;
+            tracing::debug!("attempt {attempt}: {:?}", status.lock_state);
+
+            match status.lock_state {
+                Some(state) if state == expected => return Ok(()),
+                Some(LockState::Jammed) => bail!("Lock is jammed"),
+                _ => continue,
+            }
+        }
+
+        bail!("Timed out waiting for lock state: {expected}")
     }
 }
 
@@ -31,14 +113,222 @@ impl<R> IControlDeviceUseCase for ControlDeviceUseCase<'_, R>
     async fn execute(&self, dto: ExecuteCommandDto) -> Result<()> {
         let device_id = DeviceId::new(dto.device_id);
         tracing::debug!("{:?} {:?}", device_id, dto.command);
-        self.repo.send_command(&device_id, &dto.command).await
+
+        if dto.repeat != 1 && !dto.command.kind().is_repeatable() {
+            return Err(ValidationError::new(format!(
+                "Command `{}` cannot be repeated", dto.command.kind()
+            )).into());
+        }
+
+        match self.find_device(&device_id, dto.offline).await {
+            Ok(Some(device)) => self.validate_device(&device, &dto.command, dto.offline).await?,
+            Ok(None) => {
+                tracing::warn!("{device_id:?} is not cached: skipping validation");
+            }
+            Err(e) if can_skip_lookup(&e) => {
+                tracing::warn!("{e}: skipping validation for {device_id:?}");
+            }
+            Err(e) => return Err(e),
+        }
+
+        for i in 0..dto.repeat {
+            if i > 0 {
+                tokio::time::sleep(dto.interval).await;
+            }
+            self.repo.send_command(&device_id, &dto.command).await?;
+        }
+
+        match dto.command.expected_lock_state() {
+            Some(expected) if dto.wait => self.wait_for_lock_state(&device_id, expected).await,
+            _ => Ok(()),
+        }
     }
 
     async fn fetch_devices(&self) -> Result<Vec<DeviceResponseDto>> {
         let devices = self.repo.get_device_list().await?;
-        let _ = export_devices_to_file(&devices, "output/devices.json");
+        if let Some(path) = self.cache_path {
+            let _ = export_devices_to_file(&devices, path);
+        }
 
-        let dto: Vec<DeviceResponseDto> = devices.into_iter().map(|v| v.into()).collect();
+        let dto: Vec<DeviceResponseDto> = devices
+            .into_iter()
+            .map(|v| v.try_into())
+            .collect::<Result<_>>()?;
         Ok(dto)
     }
+
+    async fn fetch_status(&self, device_id: String) -> Result<DeviceStatusResponseDto> {
+        let device_id = DeviceId::new(device_id);
+        let status = self.repo.get_device_status(&device_id).await?;
+        tracing::debug!("{:?}", status);
+
+        status.try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::errors::ApiError;
+    use crate::domain::models::entities::DeviceStatus;
+    use crate::domain::models::value_objects::{BotMode, BrightnessValue, Command, PowerState};
+    use crate::infrastructure::memory::{InMemoryDeviceRepository, RepositoryCall};
+
+    fn repo() -> InMemoryDeviceRepository {
+        InMemoryDeviceRepository::new(vec![
+            Device::new(DeviceId::new("test-light".into()), "test-light".into(), DeviceType::ColorBulb, String::new()),
+            Device::new(DeviceId::new("test-bot".into()), "test-bot".into(), DeviceType::Bot, String::new()),
+            Device::new(DeviceId::new("test-tv".into()), "test-tv".into(), DeviceType::IrTv, String::new()),
+        ])
+    }
+
+    #[tokio::test]
+    async fn execute_sends_command_and_status_reflects_it() {
+        let repo = repo();
+        let use_case = ControlDeviceUseCase::new(&repo).with_cache_path(None);
+
+        use_case
+            .execute(ExecuteCommandDto::new("test-light".into(), Command::TurnOn))
+            .await
+            .unwrap();
+
+        let status = use_case.fetch_status("test-light".into()).await.unwrap();
+        assert_eq!(status.power, Some(PowerState::On));
+        assert_eq!(repo.sent_commands().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn execute_rejects_command_unsupported_by_device() {
+        let repo = repo();
+        let use_case = ControlDeviceUseCase::new(&repo).with_cache_path(None);
+        let command = Command::SetBrightness(BrightnessValue::try_from(40).unwrap());
+
+        let err = use_case
+            .execute(ExecuteCommandDto::new("test-bot".into(), command))
+            .await
+            .unwrap_err();
+
+        assert!(err.downcast_ref::<ValidationError>().is_some());
+        assert!(repo.sent_commands().is_empty());
+    }
+
+    #[tokio::test]
+    async fn execute_rejects_switching_bot_in_press_mode() {
+        let mut status = DeviceStatus::new(
+            DeviceId::new("test-bot".into()),
+            DeviceType::Bot,
+            String::new(),
+        );
+        status.bot_mode = Some(BotMode::PressMode);
+        let repo = repo().with_status(status);
+        let use_case = ControlDeviceUseCase::new(&repo).with_cache_path(None);
+
+        let err = use_case
+            .execute(ExecuteCommandDto::new("test-bot".into(), Command::TurnOn))
+            .await
+            .unwrap_err();
+
+        assert!(err.downcast_ref::<ValidationError>().is_some());
+        assert!(repo.sent_commands().is_empty());
+    }
+
+    #[tokio::test]
+    async fn execute_repeats_infrared_command() {
+        let repo = repo();
+        let use_case = ControlDeviceUseCase::new(&repo).with_cache_path(None);
+
+        use_case
+            .execute(
+                ExecuteCommandDto::new("test-tv".into(), Command::VolumeAdd)
+                    .with_repeat(3, Duration::ZERO),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(repo.sent_commands().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn execute_rejects_repeating_non_repeatable_command() {
+        let repo = repo();
+        let use_case = ControlDeviceUseCase::new(&repo).with_cache_path(None);
+
+        let err = use_case
+            .execute(
+                ExecuteCommandDto::new("test-light".into(), Command::TurnOn)
+                    .with_repeat(2, Duration::ZERO),
+            )
+            .await
+            .unwrap_err();
+
+        assert!(err.downcast_ref::<ValidationError>().is_some());
+        assert!(repo.sent_commands().is_empty());
+    }
+
+    #[tokio::test]
+    async fn execute_propagates_repository_errors() {
+        let repo = repo();
+        repo.fail_next(
+            RepositoryCall::SendCommand,
+            ApiError::DeviceOffline { message: "offline".into() },
+        );
+        let use_case = ControlDeviceUseCase::new(&repo).with_cache_path(None);
+
+        let err = use_case
+            .execute(ExecuteCommandDto::new("test-light".into(), Command::TurnOff))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err.downcast_ref(), Some(ApiError::DeviceOffline { .. })));
+    }
+
+    #[tokio::test]
+    async fn execute_fails_for_unknown_device() {
+        let repo = repo();
+        let use_case = ControlDeviceUseCase::new(&repo).with_cache_path(None);
+
+        let err = use_case
+            .execute(ExecuteCommandDto::new("test-missing".into(), Command::TurnOn))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err.downcast_ref(), Some(ApiError::DeviceNotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn execute_sends_command_when_lookups_hit_the_budget_reserve() {
+        let repo = repo();
+        let exhausted = ApiError::BudgetExhausted { used: 9800, limit: 10000 };
+        repo.fail_next(RepositoryCall::GetDevice, exhausted.clone());
+        let use_case = ControlDeviceUseCase::new(&repo).with_cache_path(None);
+
+        use_case
+            .execute(ExecuteCommandDto::new("test-light".into(), Command::TurnOn))
+            .await
+            .unwrap();
+
+        repo.fail_next(RepositoryCall::GetDeviceStatus, exhausted);
+        use_case
+            .execute(ExecuteCommandDto::new("test-bot".into(), Command::TurnOn))
+            .await
+            .unwrap();
+
+        assert_eq!(repo.sent_commands().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn execute_offline_sends_command_without_lookups() {
+        let repo = repo();
+        let offline = ApiError::DeviceOffline { message: "unexpected lookup".into() };
+        repo.fail_next(RepositoryCall::GetDevice, offline.clone());
+        repo.fail_next(RepositoryCall::GetDeviceStatus, offline);
+        let use_case = ControlDeviceUseCase::new(&repo).with_cache_path(None);
+
+        use_case
+            .execute(ExecuteCommandDto::new("test-bot".into(), Command::TurnOn).with_offline(true))
+            .await
+            .unwrap();
+
+        assert_eq!(repo.sent_commands().len(), 1);
+    }
 }
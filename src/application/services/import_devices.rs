@@ -0,0 +1,8 @@
+use anyhow::Result;
+
+use crate::domain::models::entities::Device;
+use crate::infrastructure::io::DeviceFileReader;
+
+pub fn import_devices_from_file(path: &str) -> Result<Vec<Device>> {
+    DeviceFileReader::read_from_json(path)
+}
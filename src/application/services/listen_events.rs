@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::infrastructure::io::EventLineWriter;
+use crate::infrastructure::server::WebhookServer;
+
+#[async_trait]
+pub trait IListenEventsUseCase {
+    async fn listen(&self, addr: String, output: Option<String>) -> Result<()>;
+}
+
+#[derive(Debug, Default)]
+pub struct ListenEventsUseCase;
+
+impl ListenEventsUseCase {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl IListenEventsUseCase for ListenEventsUseCase {
+    async fn listen(&self, addr: String, output: Option<String>) -> Result<()> {
+        let writer = Arc::new(match output {
+            Some(path) => EventLineWriter::file(&path)?,
+            None => EventLineWriter::stdout(),
+        });
+
+        let server = WebhookServer::bind(&addr).await?;
+        tracing::info!("Listening on {}", server.local_addr()?);
+
+        server.run(move |event| writer.write(&event)).await
+    }
+}
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::application::dto::WebhookResponseDto;
+use crate::domain::repositories::IWebhookRepository;
+
+#[async_trait]
+pub trait IManageWebhookUseCase {
+    async fn setup(&self, url: String) -> Result<()>;
+    async fn query(&self, urls: Vec<String>) -> Result<Vec<WebhookResponseDto>>;
+    async fn update(&self, url: String, enable: bool) -> Result<()>;
+    async fn delete(&self, url: String) -> Result<()>;
+}
+
+#[derive(Debug)]
+pub struct ManageWebhookUseCase<'a, R: IWebhookRepository> {
+    repo: &'a R,
+}
+
+impl<'a, R: IWebhookRepository> ManageWebhookUseCase<'a, R> {
+    pub fn new(repo: &'a R) -> Self {
+        Self { repo }
+    }
+}
+
+#[async_trait]
+impl<R> IManageWebhookUseCase for ManageWebhookUseCase<'_, R>
+    where
+        R: IWebhookRepository + Sync + Send,
+{
+    async fn setup(&self, url: String) -> Result<()> {
+        tracing::debug!("{:?}", url);
+        self.repo.setup_webhook(&url).await
+    }
+
+    async fn query(&self, urls: Vec<String>) -> Result<Vec<WebhookResponseDto>> {
+        let urls = match urls.is_empty() {
+            true => self.repo.query_webhook_urls().await?,
+            false => urls,
+        };
+
+        if urls.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let webhooks = self.repo.query_webhook_details(&urls).await?;
+
+        let dto: Vec<WebhookResponseDto> = webhooks.into_iter().map(|v| v.into()).collect();
+        Ok(dto)
+    }
+
+    async fn update(&self, url: String, enable: bool) -> Result<()> {
+        tracing::debug!("{:?} {:?}", url, enable);
+        self.repo.update_webhook(&url, enable).await
+    }
+
+    async fn delete(&self, url: String) -> Result<()> {
+        tracing::debug!("{:?}", url);
+        self.repo.delete_webhook(&url).await
+    }
+}
@@ -0,0 +1,66 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::application::dto::{ExecuteSceneDto, SceneResponseDto};
+use crate::domain::errors::ApiError;
+use crate::domain::models::value_objects::SceneId;
+use crate::domain::repositories::ISceneRepository;
+
+#[async_trait]
+pub trait IRunSceneUseCase {
+    async fn execute(&self, dto: ExecuteSceneDto) -> Result<()>;
+    async fn fetch_scenes(&self) -> Result<Vec<SceneResponseDto>>;
+}
+
+#[derive(Debug)]
+pub struct RunSceneUseCase<'a, R: ISceneRepository> {
+    repo: &'a R,
+}
+
+impl<'a, R: ISceneRepository> RunSceneUseCase<'a, R> {
+    pub fn new(repo: &'a R) -> Self {
+        Self { repo }
+    }
+}
+
+#[async_trait]
+impl<R> IRunSceneUseCase for RunSceneUseCase<'_, R>
+    where
+        R: ISceneRepository + Sync + Send,
+{
+    async fn execute(&self, dto: ExecuteSceneDto) -> Result<()> {
+        let ExecuteSceneDto { scene, offline } = dto;
+
+        let scenes = match offline {
+            true => Ok(Vec::new()),
+            false => self.repo.get_scene_list().await,
+        };
+        let scenes = match scenes {
+            Ok(scenes) => scenes,
+            Err(e) if matches!(e.downcast_ref(), Some(ApiError::BudgetExhausted { .. })) => {
+                tracing::warn!("{e}: treating `{scene}` as a scene ID");
+                Vec::new()
+            }
+            Err(e) => return Err(e),
+        };
+
+        let scene_id = scenes
+            .into_iter()
+            .find(|v| v.name == scene)
+            .map(|v| v.id)
+            .unwrap_or_else(|| SceneId::new(scene));
+
+        tracing::debug!("{:?}", scene_id);
+        self.repo.execute_scene(&scene_id).await
+    }
+
+    async fn fetch_scenes(&self) -> Result<Vec<SceneResponseDto>> {
+        let scenes = self.repo.get_scene_list().await?;
+
+        let dto: Vec<SceneResponseDto> = scenes
+            .into_iter()
+            .map(|v| v.try_into())
+            .collect::<Result<_>>()?;
+        Ok(dto)
+    }
+}
@@ -1,4 +1,6 @@
-use anyhow::Result;
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
 use dotenvy::dotenv;
 use serde::Deserialize;
 
@@ -7,6 +9,27 @@ pub struct Config {
     pub host: String,
     pub token: String,
     pub secret: String,
+    pub retry_max_attempts: u32,
+    pub retry_base_delay_ms: u64,
+    pub retry_max_delay_ms: u64,
+    pub retry_on: Option<String>,
+    pub quota_file: String,
+    pub quota_daily_limit: u32,
+    pub quota_reserve: u32,
+    pub quota_warn_at: Vec<u8>,
+    pub connect_timeout_ms: u64,
+    pub timeout_ms: u64,
+    pub proxy: Option<String>,
+    pub no_proxy: Option<String>,
+    pub ca_bundle: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+fn env_or<T: FromStr>(key: &str, default: T) -> Result<T> {
+    match std::env::var(key) {
+        Ok(v) => v.parse::<T>().map_err(|_| anyhow!("Invalid value for {key}: {v}")),
+        Err(_) => Ok(default),
+    }
 }
 
 impl Config {
@@ -16,11 +39,43 @@ impl Config {
         let host = std::env::var("SWITCHBOT_API_HOST")?;
         let token = std::env::var("SWITCHBOT_TOKEN")?;
         let secret = std::env::var("SWITCHBOT_SECRET")?;
+        let retry_max_attempts = env_or("SWITCHBOT_RETRY_MAX_ATTEMPTS", 3)?;
+        let retry_base_delay_ms = env_or("SWITCHBOT_RETRY_BASE_DELAY_MS", 500)?;
+        let retry_max_delay_ms = env_or("SWITCHBOT_RETRY_MAX_DELAY_MS", 10_000)?;
+        let retry_on = std::env::var("SWITCHBOT_RETRY_ON").ok();
+        let quota_file = env_or("SWITCHBOT_QUOTA_FILE", "output/quota.json".to_string())?;
+        let quota_daily_limit = env_or("SWITCHBOT_QUOTA_DAILY_LIMIT", 10_000)?;
+        let quota_reserve = env_or("SWITCHBOT_QUOTA_RESERVE", 200)?;
+        let quota_warn_at = env_or("SWITCHBOT_QUOTA_WARN_AT", "80,90,95".to_string())?
+            .split(',')
+            .map(|v| v.trim().parse::<u8>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| anyhow!("Invalid value for SWITCHBOT_QUOTA_WARN_AT"))?;
+        let connect_timeout_ms = env_or("SWITCHBOT_CONNECT_TIMEOUT_MS", 10_000)?;
+        let timeout_ms = env_or("SWITCHBOT_TIMEOUT_MS", 30_000)?;
+        let proxy = std::env::var("SWITCHBOT_PROXY").ok();
+        let no_proxy = std::env::var("SWITCHBOT_NO_PROXY").ok();
+        let ca_bundle = std::env::var("SWITCHBOT_CA_BUNDLE").ok();
+        let user_agent = std::env::var("SWITCHBOT_USER_AGENT").ok();
 
         Ok(Self {
             host,
             token,
             secret,
+            retry_max_attempts,
+            retry_base_delay_ms,
+            retry_max_delay_ms,
+            retry_on,
+            quota_file,
+            quota_daily_limit,
+            quota_reserve,
+            quota_warn_at,
+            connect_timeout_ms,
+            timeout_ms,
+            proxy,
+            no_proxy,
+            ca_bundle,
+            user_agent,
         })
     }
 }
@@ -0,0 +1,79 @@
+use std::fmt;
+
+pub const STATUS_SUCCESS: i32 = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiError {
+    DeviceTypeError { message: String },
+    DeviceNotFound { message: String },
+    CommandNotSupported { message: String },
+    DeviceOffline { message: String },
+    HubOffline { message: String },
+    DeviceBusy { message: String },
+    Unauthorized,
+    RateLimited,
+    BudgetExhausted { used: u32, limit: u32 },
+    Http { status: u16 },
+    Unknown { status_code: i32, message: String },
+}
+
+impl ApiError {
+    pub fn from_status_code(status_code: i32, message: String) -> Self {
+        match status_code {
+            151 => Self::DeviceTypeError { message },
+            152 => Self::DeviceNotFound { message },
+            160 => Self::CommandNotSupported { message },
+            161 => Self::DeviceOffline { message },
+            171 => Self::HubOffline { message },
+            190 => Self::DeviceBusy { message },
+            _ => Self::Unknown { status_code, message },
+        }
+    }
+
+    pub fn from_http_status(status: u16) -> Self {
+        match status {
+            401 | 403 => Self::Unauthorized,
+            429 => Self::RateLimited,
+            _ => Self::Http { status },
+        }
+    }
+
+    pub fn status_code(&self) -> Option<i32> {
+        match self {
+            Self::DeviceTypeError { .. } => Some(151),
+            Self::DeviceNotFound { .. } => Some(152),
+            Self::CommandNotSupported { .. } => Some(160),
+            Self::DeviceOffline { .. } => Some(161),
+            Self::HubOffline { .. } => Some(171),
+            Self::DeviceBusy { .. } => Some(190),
+            Self::Unknown { status_code, .. } => Some(*status_code),
+            Self::Unauthorized
+            | Self::RateLimited
+            | Self::BudgetExhausted { .. }
+            | Self::Http { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DeviceTypeError { message } => write!(f, "Device type error (151): {message}"),
+            Self::DeviceNotFound { message } => write!(f, "Device not found (152): {message}"),
+            Self::CommandNotSupported { message } => write!(f, "Command not supported (160): {message}"),
+            Self::DeviceOffline { message } => write!(f, "Device offline (161): {message}"),
+            Self::HubOffline { message } => write!(f, "Hub device offline (171): {message}"),
+            Self::DeviceBusy { message } => write!(f, "Device internal error or busy (190): {message}"),
+            Self::Unauthorized => write!(f, "Unauthorized: check SWITCHBOT_TOKEN and SWITCHBOT_SECRET"),
+            Self::RateLimited => write!(f, "Too many requests: the daily API limit may have been exceeded"),
+            Self::BudgetExhausted { used, limit } => write!(
+                f,
+                "Daily API budget nearly exhausted ({used}/{limit} calls used): non-essential requests are refused"
+            ),
+            Self::Http { status } => write!(f, "Request failed with status: {status}"),
+            Self::Unknown { status_code, message } => write!(f, "API error ({status_code}): {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
@@ -0,0 +1,145 @@
+use anyhow::Result;
+
+use crate::domain::errors::ValidationError;
+use crate::domain::models::value_objects::{BotMode, CommandKind, DeviceType};
+
+use CommandKind::*;
+
+const SWITCH: &[CommandKind] = &[TurnOn, TurnOff];
+const BOT: &[CommandKind] = &[TurnOn, TurnOff, Press];
+const COLOR_LIGHT: &[CommandKind] = &[TurnOn, TurnOff, SetBrightness, SetColor, SetColorTemperature];
+const STRIP_LIGHT: &[CommandKind] = &[TurnOn, TurnOff, SetBrightness, SetColor];
+const CEILING_LIGHT: &[CommandKind] = &[TurnOn, TurnOff, SetBrightness, SetColorTemperature];
+const CURTAIN: &[CommandKind] = &[TurnOn, TurnOff, CurtainSetPosition];
+const BLIND_TILT: &[CommandKind] = &[TurnOn, TurnOff, BlindTiltSetPosition];
+const SMART_LOCK: &[CommandKind] = &[Lock, Unlock];
+const SMART_LOCK_PRO: &[CommandKind] = &[Lock, Unlock, Deadbolt];
+const VACUUM: &[CommandKind] = &[VacuumStart, VacuumStop, VacuumDock, VacuumPowerLevel];
+const VACUUM_V2: &[CommandKind] = &[VacuumStartClean, VacuumDock];
+const HUMIDIFIER: &[CommandKind] = &[TurnOn, TurnOff, HumidifierSetMode];
+const EVAPORATIVE_HUMIDIFIER: &[CommandKind] = &[TurnOn, TurnOff, EvaporativeHumidifierSetMode, SetChildLock];
+const IR_AIR_CONDITIONER: &[CommandKind] = &[TurnOn, TurnOff, AcSetAll];
+const IR_TV: &[CommandKind] = &[
+    TurnOn, TurnOff, SetMute, SetChannel, VolumeAdd, VolumeSub, ChannelAdd, ChannelSub,
+];
+const IR_DVD: &[CommandKind] = &[TurnOn, TurnOff, SetMute];
+const IR_SPEAKER: &[CommandKind] = &[TurnOn, TurnOff, SetMute, VolumeAdd, VolumeSub];
+const IR_FAN: &[CommandKind] = &[TurnOn, TurnOff, IrFan];
+const IR_LIGHT: &[CommandKind] = &[TurnOn, TurnOff, IrLight];
+const NONE: &[CommandKind] = &[];
+
+pub fn supported_commands(device_type: &DeviceType) -> Option<&'static [CommandKind]> {
+    let commands = match device_type {
+        DeviceType::Bot => BOT,
+
+        DeviceType::Plug
+        | DeviceType::PlugMiniUs
+        | DeviceType::PlugMiniJp
+        | DeviceType::RollerShade
+        | DeviceType::CirculatorFan
+        | DeviceType::BatteryCirculatorFan => SWITCH,
+
+        DeviceType::Curtain | DeviceType::Curtain3 => CURTAIN,
+        DeviceType::BlindTilt => BLIND_TILT,
+
+        DeviceType::Humidifier => HUMIDIFIER,
+        DeviceType::EvaporativeHumidifier => EVAPORATIVE_HUMIDIFIER,
+
+        DeviceType::ColorBulb => COLOR_LIGHT,
+        DeviceType::StripLight => STRIP_LIGHT,
+        DeviceType::CeilingLight | DeviceType::CeilingLightPro => CEILING_LIGHT,
+
+        DeviceType::SmartLock => SMART_LOCK,
+        DeviceType::SmartLockPro => SMART_LOCK_PRO,
+
+        DeviceType::RobotVacuumCleanerS1
+        | DeviceType::RobotVacuumCleanerS1Plus
+        | DeviceType::K10Plus
+        | DeviceType::K10PlusPro => VACUUM,
+        DeviceType::RobotVacuumCleanerS10 => VACUUM_V2,
+
+        DeviceType::Hub
+        | DeviceType::HubPlus
+        | DeviceType::HubMini
+        | DeviceType::Hub2
+        | DeviceType::Keypad
+        | DeviceType::KeypadTouch
+        | DeviceType::Meter
+        | DeviceType::MeterPlus
+        | DeviceType::MeterPro
+        | DeviceType::MeterProCo2
+        | DeviceType::OutdoorMeter
+        | DeviceType::MotionSensor
+        | DeviceType::ContactSensor
+        | DeviceType::WaterLeakDetector
+        | DeviceType::IndoorCam
+        | DeviceType::PanTiltCam => NONE,
+
+        DeviceType::IrAirConditioner => IR_AIR_CONDITIONER,
+        DeviceType::IrTv | DeviceType::IrStreamer | DeviceType::IrSetTopBox => IR_TV,
+        DeviceType::IrDvd => IR_DVD,
+        DeviceType::IrSpeaker => IR_SPEAKER,
+        DeviceType::IrFan => IR_FAN,
+        DeviceType::IrLight => IR_LIGHT,
+        DeviceType::IrProjector
+        | DeviceType::IrCamera
+        | DeviceType::IrAirPurifier
+        | DeviceType::IrWaterHeater
+        | DeviceType::IrRobotVacuumCleaner
+        | DeviceType::IrDiy(_) => SWITCH,
+        DeviceType::IrOthers => NONE,
+
+        DeviceType::Unknown(_) => return None,
+    };
+
+    Some(commands)
+}
+
+pub fn validate_command(device_type: &DeviceType, command: CommandKind) -> Result<()> {
+    if command == CommandKind::Custom {
+        return Ok(());
+    }
+
+    let Some(supported) = supported_commands(device_type) else {
+        return Ok(());
+    };
+
+    if !supported.contains(&command) {
+        let valid = match supported.is_empty() {
+            true => "(none)".to_string(),
+            false => supported.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", "),
+        };
+        return Err(ValidationError::new(format!(
+            "Command `{command}` is not supported by {device_type}. Supported commands: {valid}"
+        )).into());
+    }
+
+    Ok(())
+}
+
+pub fn validate_bot_mode(mode: BotMode, command: CommandKind) -> Result<()> {
+    match (mode, command) {
+        (BotMode::PressMode, TurnOn | TurnOff) => Err(ValidationError::new(format!(
+            "Command `{command}` is not available while the Bot is in {mode}. Use `press` instead"
+        )).into()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ir_tv_accepts_mute() {
+        assert!(validate_command(&DeviceType::IrTv, SetMute).is_ok());
+    }
+
+    #[test]
+    fn ir_dvd_rejects_volume_commands() {
+        assert!(validate_command(&DeviceType::IrDvd, SetMute).is_ok());
+        assert!(validate_command(&DeviceType::IrDvd, VolumeAdd).is_err());
+        assert!(validate_command(&DeviceType::IrDvd, VolumeSub).is_err());
+        assert!(validate_command(&DeviceType::IrSpeaker, VolumeAdd).is_ok());
+    }
+}
@@ -0,0 +1,15 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiQuota {
+    pub date: NaiveDate,
+    pub used: u32,
+    pub limit: u32,
+}
+
+impl ApiQuota {
+    pub fn remaining(&self) -> u32 {
+        self.limit.saturating_sub(self.used)
+    }
+}
@@ -1,12 +1,24 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::domain::models::value_objects::DeviceId;
+use crate::domain::models::value_objects::{DeviceId, DeviceType};
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Device {
     pub id: DeviceId,
     pub name: String,
-    pub device_type: String,
+    pub device_type: DeviceType,
     pub is_infrared: bool,
     pub hub_device_id: String,
 }
+
+impl Device {
+    pub fn new(id: DeviceId, name: String, device_type: DeviceType, hub_device_id: String) -> Self {
+        Self {
+            id,
+            name,
+            is_infrared: device_type.is_infrared(),
+            device_type,
+            hub_device_id,
+        }
+    }
+}
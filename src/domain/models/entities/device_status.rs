@@ -0,0 +1,48 @@
+use serde::Serialize;
+
+use crate::domain::models::value_objects::{
+    BotMode, DeviceId, DeviceType, DoorState, LockState, PowerState,
+};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceStatus {
+    pub id: DeviceId,
+    pub device_type: DeviceType,
+    pub hub_device_id: String,
+    pub power: Option<PowerState>,
+    pub brightness: Option<u8>,
+    pub color: Option<(u8, u8, u8)>,
+    pub color_temperature: Option<u16>,
+    pub temperature: Option<f32>,
+    pub humidity: Option<u8>,
+    pub battery: Option<u8>,
+    pub slide_position: Option<u8>,
+    pub moving: Option<bool>,
+    pub lock_state: Option<LockState>,
+    pub door_state: Option<DoorState>,
+    pub bot_mode: Option<BotMode>,
+    pub version: Option<String>,
+}
+
+impl DeviceStatus {
+    pub fn new(id: DeviceId, device_type: DeviceType, hub_device_id: String) -> Self {
+        Self {
+            id,
+            device_type,
+            hub_device_id,
+            power: None,
+            brightness: None,
+            color: None,
+            color_temperature: None,
+            temperature: None,
+            humidity: None,
+            battery: None,
+            slide_position: None,
+            moving: None,
+            lock_state: None,
+            door_state: None,
+            bot_mode: None,
+            version: None,
+        }
+    }
+}
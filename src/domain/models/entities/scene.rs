@@ -0,0 +1,9 @@
+use serde::Serialize;
+
+use crate::domain::models::value_objects::SceneId;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Scene {
+    pub id: SceneId,
+    pub name: String,
+}
@@ -0,0 +1,11 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Webhook {
+    pub url: String,
+    pub device_list: String,
+    pub enable: bool,
+    pub create_time: Option<DateTime<Utc>>,
+    pub last_update_time: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,35 @@
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum DeviceEvent {
+    Motion {
+        device_mac: String,
+        detected: bool,
+        time_of_sample: i64,
+    },
+    Contact {
+        device_mac: String,
+        detected: bool,
+        open_state: String,
+        time_of_sample: i64,
+    },
+    Lock {
+        device_mac: String,
+        lock_state: String,
+        time_of_sample: i64,
+    },
+    Meter {
+        device_mac: String,
+        temperature: f32,
+        humidity: u8,
+        scale: String,
+        time_of_sample: i64,
+    },
+    Other {
+        device_type: String,
+        device_mac: String,
+        context: Value,
+    },
+}
@@ -0,0 +1,13 @@
+use serde::Serialize;
+use strum::{Display, EnumString};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, EnumString, Display)]
+#[serde(rename_all = "camelCase")]
+pub enum BotMode {
+    #[strum(serialize = "pressMode")]
+    PressMode,
+    #[strum(serialize = "switchMode")]
+    SwitchMode,
+    #[strum(serialize = "customizeMode")]
+    CustomizeMode,
+}
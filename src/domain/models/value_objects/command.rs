@@ -5,6 +5,8 @@ use num_enum::TryFromPrimitive;
 use serde_json::Value;
 use strum::{Display, EnumString};
 
+use crate::domain::models::value_objects::LockState;
+
 #[derive(Debug, Clone)]
 pub enum Command {
     TurnOn,
@@ -13,13 +15,176 @@ pub enum Command {
     SetColor(ColorValues),
     SetColorTemperature(ColorTemperatureValue),
     AcSetAll(AcValues),
+    CurtainSetPosition(CurtainPositionValues),
+    BlindTiltSetPosition(BlindTiltPositionValues),
+    Lock,
+    Unlock,
+    Deadbolt,
+    Press,
+    VacuumStart,
+    VacuumStop,
+    VacuumDock,
+    VacuumPowerLevel(VacuumPowerLevelValue),
+    VacuumStartClean(VacuumCleanValues),
+    HumidifierSetMode(HumidifierMode),
+    EvaporativeHumidifierSetMode(EvaporativeHumidifierValues),
+    SetChildLock(bool),
+    SetChannel(ChannelValue),
+    VolumeAdd,
+    VolumeSub,
+    ChannelAdd,
+    ChannelSub,
+    SetMute,
+    IrFan(IrFanAction),
+    IrLight(IrLightAction),
     Custom {
         command_type: String,
         command: String,
-        parameter: String,
+        parameter: Value,
     },
 }
 
+impl Command {
+    pub fn kind(&self) -> CommandKind {
+        match self {
+            Command::TurnOn => CommandKind::TurnOn,
+            Command::TurnOff => CommandKind::TurnOff,
+            Command::SetBrightness(_) => CommandKind::SetBrightness,
+            Command::SetColor(_) => CommandKind::SetColor,
+            Command::SetColorTemperature(_) => CommandKind::SetColorTemperature,
+            Command::AcSetAll(_) => CommandKind::AcSetAll,
+            Command::CurtainSetPosition(_) => CommandKind::CurtainSetPosition,
+            Command::BlindTiltSetPosition(_) => CommandKind::BlindTiltSetPosition,
+            Command::Lock => CommandKind::Lock,
+            Command::Unlock => CommandKind::Unlock,
+            Command::Deadbolt => CommandKind::Deadbolt,
+            Command::Press => CommandKind::Press,
+            Command::VacuumStart => CommandKind::VacuumStart,
+            Command::VacuumStop => CommandKind::VacuumStop,
+            Command::VacuumDock => CommandKind::VacuumDock,
+            Command::VacuumPowerLevel(_) => CommandKind::VacuumPowerLevel,
+            Command::VacuumStartClean(_) => CommandKind::VacuumStartClean,
+            Command::HumidifierSetMode(_) => CommandKind::HumidifierSetMode,
+            Command::EvaporativeHumidifierSetMode(_) => CommandKind::EvaporativeHumidifierSetMode,
+            Command::SetChildLock(_) => CommandKind::SetChildLock,
+            Command::SetChannel(_) => CommandKind::SetChannel,
+            Command::VolumeAdd => CommandKind::VolumeAdd,
+            Command::VolumeSub => CommandKind::VolumeSub,
+            Command::ChannelAdd => CommandKind::ChannelAdd,
+            Command::ChannelSub => CommandKind::ChannelSub,
+            Command::SetMute => CommandKind::SetMute,
+            Command::IrFan(_) => CommandKind::IrFan,
+            Command::IrLight(_) => CommandKind::IrLight,
+            Command::Custom { .. } => CommandKind::Custom,
+        }
+    }
+
+    pub fn expected_lock_state(&self) -> Option<LockState> {
+        match self {
+            Command::Lock | Command::Deadbolt => Some(LockState::Locked),
+            Command::Unlock => Some(LockState::Unlocked),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+pub enum CommandKind {
+    #[strum(serialize = "on")]
+    TurnOn,
+    #[strum(serialize = "off")]
+    TurnOff,
+    #[strum(serialize = "brightness")]
+    SetBrightness,
+    #[strum(serialize = "color")]
+    SetColor,
+    #[strum(serialize = "color_temp")]
+    SetColorTemperature,
+    #[strum(serialize = "ac")]
+    AcSetAll,
+    #[strum(serialize = "curtain")]
+    CurtainSetPosition,
+    #[strum(serialize = "blind")]
+    BlindTiltSetPosition,
+    #[strum(serialize = "lock")]
+    Lock,
+    #[strum(serialize = "unlock")]
+    Unlock,
+    #[strum(serialize = "deadbolt")]
+    Deadbolt,
+    #[strum(serialize = "press")]
+    Press,
+    #[strum(serialize = "vacuum start")]
+    VacuumStart,
+    #[strum(serialize = "vacuum stop")]
+    VacuumStop,
+    #[strum(serialize = "vacuum dock")]
+    VacuumDock,
+    #[strum(serialize = "vacuum power-level")]
+    VacuumPowerLevel,
+    #[strum(serialize = "vacuum clean")]
+    VacuumStartClean,
+    #[strum(serialize = "humidifier")]
+    HumidifierSetMode,
+    #[strum(serialize = "evap_humidifier")]
+    EvaporativeHumidifierSetMode,
+    #[strum(serialize = "child_lock")]
+    SetChildLock,
+    #[strum(serialize = "channel")]
+    SetChannel,
+    #[strum(serialize = "volume_up")]
+    VolumeAdd,
+    #[strum(serialize = "volume_down")]
+    VolumeSub,
+    #[strum(serialize = "channel_up")]
+    ChannelAdd,
+    #[strum(serialize = "channel_down")]
+    ChannelSub,
+    #[strum(serialize = "mute")]
+    SetMute,
+    #[strum(serialize = "swing/timer/low_speed/middle_speed/high_speed")]
+    IrFan,
+    #[strum(serialize = "brightness_up/brightness_down")]
+    IrLight,
+    #[strum(serialize = "custom")]
+    Custom,
+}
+
+impl CommandKind {
+    pub fn is_infrared_only(&self) -> bool {
+        matches!(
+            self,
+            CommandKind::SetChannel
+                | CommandKind::VolumeAdd
+                | CommandKind::VolumeSub
+                | CommandKind::ChannelAdd
+                | CommandKind::ChannelSub
+                | CommandKind::SetMute
+                | CommandKind::IrFan
+                | CommandKind::IrLight
+        )
+    }
+
+    pub fn is_repeatable(&self) -> bool {
+        matches!(
+            self,
+            CommandKind::VolumeAdd
+                | CommandKind::VolumeSub
+                | CommandKind::ChannelAdd
+                | CommandKind::ChannelSub
+                | CommandKind::IrLight
+        )
+    }
+}
+
+pub fn parse_parameter(raw: &str) -> Value {
+    match serde_json::from_str::<Value>(raw) {
+        Ok(Value::Null) => Value::String("default".into()),
+        Ok(value) => value,
+        Err(_) => Value::String(raw.to_string()),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BrightnessValue(u8);
 impl BrightnessValue {
@@ -37,7 +202,7 @@ impl TryFrom<u8> for BrightnessValue {
 
         (inf..=sup)
             .contains(&value)
-            .then(|| Self(value))
+            .then_some(Self(value))
             .ok_or_else(|| anyhow!("Value must be between {inf} and {sup}"))
     }
 }
@@ -70,7 +235,7 @@ impl TryFrom<(u8, u8, u8)> for ColorValues {
         [r, g, b]
             .iter()
             .all(|&v| (0..=255).contains(&v))
-            .then(|| Self(r, g, b))
+            .then_some(Self(r, g, b))
             .ok_or_else(|| anyhow!("color values must be between 0 and 255"))
     }
 }
@@ -91,7 +256,7 @@ impl TryFrom<u16> for ColorTemperatureValue {
 
         (inf..=sup)
             .contains(&value)
-            .then(|| Self(value))
+            .then_some(Self(value))
             .ok_or_else(|| anyhow!("Value must be between {inf} and {sup}"))
     }
 }
@@ -141,3 +306,376 @@ pub enum AcPowerState {
     ON,
 }
 
+
+#[derive(Debug, Clone)]
+pub struct PositionValue(u8);
+impl PositionValue {
+    pub fn get(&self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u8> for PositionValue {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        let inf = 0;
+        let sup = 100;
+
+        (inf..=sup)
+            .contains(&value)
+            .then_some(Self(value))
+            .ok_or_else(|| anyhow!("Value must be between {inf} and {sup}"))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CurtainPositionValues {
+    pub index: u8,
+    pub mode: CurtainMode,
+    pub position: PositionValue,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
+pub enum CurtainMode {
+    #[strum(to_string = "0", serialize = "performance")]
+    PERFORMANCE,
+    #[strum(to_string = "1", serialize = "silent")]
+    SILENT,
+    #[strum(to_string = "ff", serialize = "default")]
+    DEFAULT,
+}
+
+#[derive(Debug, Clone)]
+pub struct TiltAngleValue(u8);
+impl TiltAngleValue {
+    pub fn get(&self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u8> for TiltAngleValue {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        let inf = 0;
+        let sup = 100;
+
+        ((inf..=sup).contains(&value) && value.is_multiple_of(2))
+            .then_some(Self(value))
+            .ok_or_else(|| anyhow!("Value must be an even number between {inf} and {sup}"))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BlindTiltPositionValues {
+    pub direction: BlindTiltDirection,
+    pub angle: TiltAngleValue,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
+pub enum BlindTiltDirection {
+    #[strum(serialize = "up")]
+    UP,
+    #[strum(serialize = "down")]
+    DOWN,
+}
+
+#[derive(Debug, Clone)]
+pub struct VacuumPowerLevelValue(u8);
+impl VacuumPowerLevelValue {
+    pub fn get(&self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u8> for VacuumPowerLevelValue {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        let inf = 0;
+        let sup = 3;
+
+        (inf..=sup)
+            .contains(&value)
+            .then_some(Self(value))
+            .ok_or_else(|| anyhow!("Value must be between {inf} and {sup}"))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VacuumCleanValues {
+    pub action: VacuumCleanAction,
+    pub fan_level: VacuumFanLevelValue,
+    pub water_level: VacuumWaterLevelValue,
+    pub times: VacuumCleanTimesValue,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
+pub enum VacuumCleanAction {
+    #[strum(serialize = "sweep")]
+    SWEEP,
+    #[strum(serialize = "mop")]
+    MOP,
+    #[strum(serialize = "sweep_mop")]
+    SWEEP_MOP,
+}
+
+#[derive(Debug, Clone)]
+pub struct VacuumFanLevelValue(u8);
+impl VacuumFanLevelValue {
+    pub fn get(&self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u8> for VacuumFanLevelValue {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        let inf = 1;
+        let sup = 4;
+
+        (inf..=sup)
+            .contains(&value)
+            .then_some(Self(value))
+            .ok_or_else(|| anyhow!("Value must be between {inf} and {sup}"))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VacuumWaterLevelValue(u8);
+impl VacuumWaterLevelValue {
+    pub fn get(&self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u8> for VacuumWaterLevelValue {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        let inf = 1;
+        let sup = 2;
+
+        (inf..=sup)
+            .contains(&value)
+            .then_some(Self(value))
+            .ok_or_else(|| anyhow!("Value must be between {inf} and {sup}"))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VacuumCleanTimesValue(u32);
+impl VacuumCleanTimesValue {
+    pub fn get(&self) -> u32 {
+        self.0
+    }
+}
+
+impl TryFrom<u32> for VacuumCleanTimesValue {
+    type Error = Error;
+
+    fn try_from(value: u32) -> Result<Self> {
+        let inf = 1;
+        let sup = 2639999;
+
+        (inf..=sup)
+            .contains(&value)
+            .then_some(Self(value))
+            .ok_or_else(|| anyhow!("Value must be between {inf} and {sup}"))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HumidityValue(u8);
+impl HumidityValue {
+    pub fn get(&self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u8> for HumidityValue {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        let inf = 0;
+        let sup = 100;
+
+        (inf..=sup)
+            .contains(&value)
+            .then_some(Self(value))
+            .ok_or_else(|| anyhow!("Value must be between {inf} and {sup}"))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum HumidifierMode {
+    AUTO,
+    LOW,
+    MEDIUM,
+    HIGH,
+    TARGET(HumidityValue),
+}
+
+impl FromStr for HumidifierMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "auto" => Ok(Self::AUTO),
+            "low" | "101" => Ok(Self::LOW),
+            "medium" | "102" => Ok(Self::MEDIUM),
+            "high" | "103" => Ok(Self::HIGH),
+            other => Ok(Self::TARGET(HumidityValue::try_from(other.parse::<u8>()?)?)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EvaporativeHumidifierValues {
+    pub mode: EvaporativeHumidifierMode,
+    pub target_humidity: HumidityValue,
+}
+
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, TryFromPrimitive, EnumString, Display)]
+pub enum EvaporativeHumidifierMode {
+    #[strum(serialize = "level4", serialize = "1")]
+    LEVEL4 = 1,
+    #[strum(serialize = "level3", serialize = "2")]
+    LEVEL3 = 2,
+    #[strum(serialize = "level2", serialize = "3")]
+    LEVEL2 = 3,
+    #[strum(serialize = "level1", serialize = "4")]
+    LEVEL1 = 4,
+    #[strum(serialize = "humidity", serialize = "5")]
+    HUMIDITY = 5,
+    #[strum(serialize = "sleep", serialize = "6")]
+    SLEEP = 6,
+    #[strum(serialize = "auto", serialize = "7")]
+    AUTO = 7,
+    #[strum(serialize = "drying", serialize = "8")]
+    DRYING = 8,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChannelValue(u16);
+impl ChannelValue {
+    pub fn get(&self) -> u16 {
+        self.0
+    }
+}
+
+impl TryFrom<u16> for ChannelValue {
+    type Error = Error;
+
+    fn try_from(value: u16) -> Result<Self> {
+        let inf = 1;
+        let sup = 9999;
+
+        (inf..=sup)
+            .contains(&value)
+            .then_some(Self(value))
+            .ok_or_else(|| anyhow!("Value must be between {inf} and {sup}"))
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
+pub enum IrFanAction {
+    #[strum(serialize = "swing")]
+    SWING,
+    #[strum(serialize = "timer")]
+    TIMER,
+    #[strum(to_string = "lowSpeed", serialize = "low_speed")]
+    LOW_SPEED,
+    #[strum(to_string = "middleSpeed", serialize = "middle_speed")]
+    MIDDLE_SPEED,
+    #[strum(to_string = "highSpeed", serialize = "high_speed")]
+    HIGH_SPEED,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
+pub enum IrLightAction {
+    #[strum(to_string = "brightnessUp", serialize = "brightness_up")]
+    BRIGHTNESS_UP,
+    #[strum(to_string = "brightnessDown", serialize = "brightness_down")]
+    BRIGHTNESS_DOWN,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn check_bounds<T, V>(valid: &[V], invalid: &[V])
+    where
+        T: TryFrom<V, Error = Error>,
+        V: Copy + std::fmt::Debug,
+    {
+        for value in valid {
+            assert!(T::try_from(*value).is_ok(), "{value:?} should be accepted");
+        }
+        for value in invalid {
+            assert!(T::try_from(*value).is_err(), "{value:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn position_values_enforce_their_bounds() {
+        check_bounds::<PositionValue, u8>(&[0, 50, 100], &[101, 255]);
+        check_bounds::<TiltAngleValue, u8>(&[0, 2, 100], &[1, 99, 102]);
+    }
+
+    #[test]
+    fn curtain_mode_parses_names_and_displays_wire_values() {
+        assert_eq!("performance".parse::<CurtainMode>().unwrap(), CurtainMode::PERFORMANCE);
+        assert_eq!("silent".parse::<CurtainMode>().unwrap(), CurtainMode::SILENT);
+        assert_eq!(CurtainMode::DEFAULT.to_string(), "ff");
+        assert!("fast".parse::<CurtainMode>().is_err());
+    }
+
+    #[test]
+    fn vacuum_values_enforce_their_bounds() {
+        check_bounds::<VacuumPowerLevelValue, u8>(&[0, 3], &[4]);
+        check_bounds::<VacuumFanLevelValue, u8>(&[1, 4], &[0, 5]);
+        check_bounds::<VacuumWaterLevelValue, u8>(&[1, 2], &[0, 3]);
+        check_bounds::<VacuumCleanTimesValue, u32>(&[1, 2639999], &[0, 2640000]);
+    }
+
+    #[test]
+    fn humidifier_values_enforce_their_bounds() {
+        check_bounds::<HumidityValue, u8>(&[0, 100], &[101]);
+
+        assert!(matches!("102".parse::<HumidifierMode>().unwrap(), HumidifierMode::MEDIUM));
+        assert!(matches!("45".parse::<HumidifierMode>().unwrap(), HumidifierMode::TARGET(v) if v.get() == 45));
+        assert!("101%".parse::<HumidifierMode>().is_err());
+        assert_eq!("auto".parse::<EvaporativeHumidifierMode>().unwrap() as u8, 7);
+        assert_eq!("1".parse::<EvaporativeHumidifierMode>().unwrap(), EvaporativeHumidifierMode::LEVEL4);
+        assert!("level5".parse::<EvaporativeHumidifierMode>().is_err());
+    }
+
+    #[test]
+    fn parse_parameter_distinguishes_strings_numbers_and_json() {
+        let cases = [
+            ("on", json!("on")),
+            ("\"on\"", json!("on")),
+            ("\"42\"", json!("42")),
+            ("42", json!(42)),
+            ("-1.5", json!(-1.5)),
+            ("true", json!(true)),
+            ("null", json!("default")),
+            ("{\"mode\":2}", json!({ "mode": 2 })),
+            ("[1,2]", json!([1, 2])),
+            ("26,1,3,on", json!("26,1,3,on")),
+        ];
+
+        for (raw, expected) in cases {
+            assert_eq!(parse_parameter(raw), expected, "{raw}");
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+const DIY_PREFIX: &str = "DIY ";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DeviceType {
+    Hub,
+    HubPlus,
+    HubMini,
+    Hub2,
+    Bot,
+    Curtain,
+    Curtain3,
+    BlindTilt,
+    RollerShade,
+    Plug,
+    PlugMiniUs,
+    PlugMiniJp,
+    ColorBulb,
+    StripLight,
+    CeilingLight,
+    CeilingLightPro,
+    SmartLock,
+    SmartLockPro,
+    Keypad,
+    KeypadTouch,
+    Meter,
+    MeterPlus,
+    MeterPro,
+    MeterProCo2,
+    OutdoorMeter,
+    MotionSensor,
+    ContactSensor,
+    WaterLeakDetector,
+    RobotVacuumCleanerS1,
+    RobotVacuumCleanerS1Plus,
+    RobotVacuumCleanerS10,
+    K10Plus,
+    K10PlusPro,
+    Humidifier,
+    EvaporativeHumidifier,
+    CirculatorFan,
+    BatteryCirculatorFan,
+    IndoorCam,
+    PanTiltCam,
+
+    IrAirConditioner,
+    IrTv,
+    IrLight,
+    IrStreamer,
+    IrSetTopBox,
+    IrDvd,
+    IrFan,
+    IrProjector,
+    IrCamera,
+    IrAirPurifier,
+    IrSpeaker,
+    IrWaterHeater,
+    IrRobotVacuumCleaner,
+    IrOthers,
+    IrDiy(String),
+
+    Unknown(String),
+}
+
+impl DeviceType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Hub => "Hub",
+            Self::HubPlus => "Hub Plus",
+            Self::HubMini => "Hub Mini",
+            Self::Hub2 => "Hub 2",
+            Self::Bot => "Bot",
+            Self::Curtain => "Curtain",
+            Self::Curtain3 => "Curtain3",
+            Self::BlindTilt => "Blind Tilt",
+            Self::RollerShade => "Roller Shade",
+            Self::Plug => "Plug",
+            Self::PlugMiniUs => "Plug Mini (US)",
+            Self::PlugMiniJp => "Plug Mini (JP)",
+            Self::ColorBulb => "Color Bulb",
+            Self::StripLight => "Strip Light",
+            Self::CeilingLight => "Ceiling Light",
+            Self::CeilingLightPro => "Ceiling Light Pro",
+            Self::SmartLock => "Smart Lock",
+            Self::SmartLockPro => "Smart Lock Pro",
+            Self::Keypad => "Keypad",
+            Self::KeypadTouch => "Keypad Touch",
+            Self::Meter => "Meter",
+            Self::MeterPlus => "MeterPlus",
+            Self::MeterPro => "MeterPro",
+            Self::MeterProCo2 => "MeterPro(CO2)",
+            Self::OutdoorMeter => "WoIOSensor",
+            Self::MotionSensor => "Motion Sensor",
+            Self::ContactSensor => "Contact Sensor",
+            Self::WaterLeakDetector => "Water Detector",
+            Self::RobotVacuumCleanerS1 => "Robot Vacuum Cleaner S1",
+            Self::RobotVacuumCleanerS1Plus => "Robot Vacuum Cleaner S1 Plus",
+            Self::RobotVacuumCleanerS10 => "Robot Vacuum Cleaner S10",
+            Self::K10Plus => "K10+",
+            Self::K10PlusPro => "K10+ Pro",
+            Self::Humidifier => "Humidifier",
+            Self::EvaporativeHumidifier => "Humidifier2",
+            Self::CirculatorFan => "Circulator Fan",
+            Self::BatteryCirculatorFan => "Battery Circulator Fan",
+            Self::IndoorCam => "Indoor Cam",
+            Self::PanTiltCam => "Pan/Tilt Cam",
+
+            Self::IrAirConditioner => "Air Conditioner",
+            Self::IrTv => "TV",
+            Self::IrLight => "Light",
+            Self::IrStreamer => "Streamer",
+            Self::IrSetTopBox => "Set Top Box",
+            Self::IrDvd => "DVD",
+            Self::IrFan => "Fan",
+            Self::IrProjector => "Projector",
+            Self::IrCamera => "Camera",
+            Self::IrAirPurifier => "Air Purifier",
+            Self::IrSpeaker => "Speaker",
+            Self::IrWaterHeater => "Water Heater",
+            Self::IrRobotVacuumCleaner => "Robot Vacuum Cleaner",
+            Self::IrOthers => "Others",
+            Self::IrDiy(value) => value,
+
+            Self::Unknown(value) => value,
+        }
+    }
+
+    pub fn is_infrared(&self) -> bool {
+        matches!(
+            self,
+            Self::IrAirConditioner
+                | Self::IrTv
+                | Self::IrLight
+                | Self::IrStreamer
+                | Self::IrSetTopBox
+                | Self::IrDvd
+                | Self::IrFan
+                | Self::IrProjector
+                | Self::IrCamera
+                | Self::IrAirPurifier
+                | Self::IrSpeaker
+                | Self::IrWaterHeater
+                | Self::IrRobotVacuumCleaner
+                | Self::IrOthers
+                | Self::IrDiy(_)
+        )
+    }
+}
+
+impl From<&str> for DeviceType {
+    fn from(value: &str) -> Self {
+        match value {
+            "Hub" => Self::Hub,
+            "Hub Plus" => Self::HubPlus,
+            "Hub Mini" => Self::HubMini,
+            "Hub 2" => Self::Hub2,
+            "Bot" => Self::Bot,
+            "Curtain" => Self::Curtain,
+            "Curtain3" => Self::Curtain3,
+            "Blind Tilt" => Self::BlindTilt,
+            "Roller Shade" => Self::RollerShade,
+            "Plug" => Self::Plug,
+            "Plug Mini (US)" => Self::PlugMiniUs,
+            "Plug Mini (JP)" => Self::PlugMiniJp,
+            "Color Bulb" => Self::ColorBulb,
+            "Strip Light" => Self::StripLight,
+            "Ceiling Light" => Self::CeilingLight,
+            "Ceiling Light Pro" => Self::CeilingLightPro,
+            "Smart Lock" => Self::SmartLock,
+            "Smart Lock Pro" => Self::SmartLockPro,
+            "Keypad" => Self::Keypad,
+            "Keypad Touch" => Self::KeypadTouch,
+            "Meter" => Self::Meter,
+            "MeterPlus" => Self::MeterPlus,
+            "MeterPro" => Self::MeterPro,
+            "MeterPro(CO2)" => Self::MeterProCo2,
+            "WoIOSensor" => Self::OutdoorMeter,
+            "Motion Sensor" => Self::MotionSensor,
+            "Contact Sensor" => Self::ContactSensor,
+            "Water Detector" => Self::WaterLeakDetector,
+            "Robot Vacuum Cleaner S1" => Self::RobotVacuumCleanerS1,
+            "Robot Vacuum Cleaner S1 Plus" => Self::RobotVacuumCleanerS1Plus,
+            "Robot Vacuum Cleaner S10" => Self::RobotVacuumCleanerS10,
+            "K10+" => Self::K10Plus,
+            "K10+ Pro" => Self::K10PlusPro,
+            "Humidifier" => Self::Humidifier,
+            "Humidifier2" => Self::EvaporativeHumidifier,
+            "Circulator Fan" => Self::CirculatorFan,
+            "Battery Circulator Fan" => Self::BatteryCirculatorFan,
+            "Indoor Cam" => Self::IndoorCam,
+            "Pan/Tilt Cam" => Self::PanTiltCam,
+
+            "Air Conditioner" => Self::IrAirConditioner,
+            "TV" => Self::IrTv,
+            "Light" => Self::IrLight,
+            "Streamer" => Self::IrStreamer,
+            "Set Top Box" => Self::IrSetTopBox,
+            "DVD" => Self::IrDvd,
+            "Fan" => Self::IrFan,
+            "Projector" => Self::IrProjector,
+            "Camera" => Self::IrCamera,
+            "Air Purifier" => Self::IrAirPurifier,
+            "Speaker" => Self::IrSpeaker,
+            "Water Heater" => Self::IrWaterHeater,
+            "Robot Vacuum Cleaner" => Self::IrRobotVacuumCleaner,
+            "Others" => Self::IrOthers,
+            v if v.starts_with(DIY_PREFIX) => Self::IrDiy(v.to_string()),
+
+            v => Self::Unknown(v.to_string()),
+        }
+    }
+}
+
+impl From<String> for DeviceType {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl FromStr for DeviceType {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(s))
+    }
+}
+
+impl fmt::Display for DeviceType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for DeviceType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for DeviceType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self::from(String::deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KNOWN: &[DeviceType] = &[
+        DeviceType::Hub,
+        DeviceType::HubPlus,
+        DeviceType::HubMini,
+        DeviceType::Hub2,
+        DeviceType::Bot,
+        DeviceType::Curtain,
+        DeviceType::Curtain3,
+        DeviceType::BlindTilt,
+        DeviceType::RollerShade,
+        DeviceType::Plug,
+        DeviceType::PlugMiniUs,
+        DeviceType::PlugMiniJp,
+        DeviceType::ColorBulb,
+        DeviceType::StripLight,
+        DeviceType::CeilingLight,
+        DeviceType::CeilingLightPro,
+        DeviceType::SmartLock,
+        DeviceType::SmartLockPro,
+        DeviceType::Keypad,
+        DeviceType::KeypadTouch,
+        DeviceType::Meter,
+        DeviceType::MeterPlus,
+        DeviceType::MeterPro,
+        DeviceType::MeterProCo2,
+        DeviceType::OutdoorMeter,
+        DeviceType::MotionSensor,
+        DeviceType::ContactSensor,
+        DeviceType::WaterLeakDetector,
+        DeviceType::RobotVacuumCleanerS1,
+        DeviceType::RobotVacuumCleanerS1Plus,
+        DeviceType::RobotVacuumCleanerS10,
+        DeviceType::K10Plus,
+        DeviceType::K10PlusPro,
+        DeviceType::Humidifier,
+        DeviceType::EvaporativeHumidifier,
+        DeviceType::CirculatorFan,
+        DeviceType::BatteryCirculatorFan,
+        DeviceType::IndoorCam,
+        DeviceType::PanTiltCam,
+        DeviceType::IrAirConditioner,
+        DeviceType::IrTv,
+        DeviceType::IrLight,
+        DeviceType::IrStreamer,
+        DeviceType::IrSetTopBox,
+        DeviceType::IrDvd,
+        DeviceType::IrFan,
+        DeviceType::IrProjector,
+        DeviceType::IrCamera,
+        DeviceType::IrAirPurifier,
+        DeviceType::IrSpeaker,
+        DeviceType::IrWaterHeater,
+        DeviceType::IrRobotVacuumCleaner,
+        DeviceType::IrOthers,
+    ];
+
+    #[test]
+    fn known_types_round_trip_through_display() {
+        for device_type in KNOWN {
+            let parsed: DeviceType = device_type.to_string().parse().unwrap();
+            assert_eq!(&parsed, device_type, "{device_type}");
+        }
+    }
+
+    #[test]
+    fn diy_remotes_keep_their_name() {
+        let parsed: DeviceType = "DIY Projector".parse().unwrap();
+
+        assert_eq!(parsed, DeviceType::IrDiy("DIY Projector".into()));
+        assert_eq!(parsed.to_string(), "DIY Projector");
+        assert!(parsed.is_infrared());
+    }
+
+    #[test]
+    fn unrecognised_types_fall_back_to_unknown() {
+        let parsed: DeviceType = "Smart Toaster".parse().unwrap();
+
+        assert_eq!(parsed, DeviceType::Unknown("Smart Toaster".into()));
+        assert_eq!(parsed.to_string(), "Smart Toaster");
+        assert!(!parsed.is_infrared());
+    }
+}
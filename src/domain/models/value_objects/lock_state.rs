@@ -0,0 +1,22 @@
+use serde::Serialize;
+use strum::{Display, EnumString};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, EnumString, Display)]
+#[serde(rename_all = "lowercase")]
+pub enum LockState {
+    #[strum(serialize = "locked", ascii_case_insensitive)]
+    Locked,
+    #[strum(serialize = "unlocked", ascii_case_insensitive)]
+    Unlocked,
+    #[strum(serialize = "jammed", ascii_case_insensitive)]
+    Jammed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, EnumString, Display)]
+#[serde(rename_all = "lowercase")]
+pub enum DoorState {
+    #[strum(serialize = "opened", serialize = "open", ascii_case_insensitive)]
+    Opened,
+    #[strum(serialize = "closed", serialize = "close", ascii_case_insensitive)]
+    Closed,
+}
@@ -0,0 +1,11 @@
+use serde::Serialize;
+use strum::{Display, EnumString};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, EnumString, Display)]
+#[serde(rename_all = "lowercase")]
+pub enum PowerState {
+    #[strum(serialize = "on", ascii_case_insensitive)]
+    On,
+    #[strum(serialize = "off", ascii_case_insensitive)]
+    Off,
+}
@@ -0,0 +1,15 @@
+use anyhow::Result;
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct SceneId(String);
+
+impl SceneId {
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+
+    pub fn value(&self) -> Result<String> {
+        Ok(self.0.clone())
+    }
+}
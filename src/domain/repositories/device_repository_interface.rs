@@ -1,12 +1,13 @@
 use anyhow::Result;
 use async_trait::async_trait;
 
-use crate::domain::models::entities::Device;
+use crate::domain::models::entities::{Device, DeviceStatus};
 use crate::domain::models::value_objects::{Command, DeviceId};
 
 #[async_trait]
 pub trait IDeviceRepository {
     async fn get_device(&self, id: &DeviceId) -> Result<Device>;
+    async fn get_device_status(&self, id: &DeviceId) -> Result<DeviceStatus>;
     async fn send_command(&self, id: &DeviceId, command: &Command) -> Result<()>;
     async fn get_device_list(&self) -> Result<Vec<Device>>;
 }
@@ -0,0 +1,9 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::domain::models::entities::ApiQuota;
+
+#[async_trait]
+pub trait IQuotaRepository {
+    async fn get_quota(&self) -> Result<ApiQuota>;
+}
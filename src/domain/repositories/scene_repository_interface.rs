@@ -0,0 +1,11 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::domain::models::entities::Scene;
+use crate::domain::models::value_objects::SceneId;
+
+#[async_trait]
+pub trait ISceneRepository {
+    async fn get_scene_list(&self) -> Result<Vec<Scene>>;
+    async fn execute_scene(&self, id: &SceneId) -> Result<()>;
+}
@@ -0,0 +1,13 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::domain::models::entities::Webhook;
+
+#[async_trait]
+pub trait IWebhookRepository {
+    async fn setup_webhook(&self, url: &str) -> Result<()>;
+    async fn query_webhook_urls(&self) -> Result<Vec<String>>;
+    async fn query_webhook_details(&self, urls: &[String]) -> Result<Vec<Webhook>>;
+    async fn update_webhook(&self, url: &str, enable: bool) -> Result<()>;
+    async fn delete_webhook(&self, url: &str) -> Result<()>;
+}
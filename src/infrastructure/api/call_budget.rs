@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+
+use crate::domain::errors::ApiError;
+use crate::domain::models::entities::ApiQuota;
+use crate::infrastructure::io::{QuotaFileStore, QuotaRecord};
+
+#[derive(Debug, Clone)]
+pub struct CallBudget {
+    pub path: String,
+    pub daily_limit: u32,
+    pub reserve: u32,
+    pub warn_at: Vec<u8>,
+}
+
+impl Default for CallBudget {
+    fn default() -> Self {
+        Self {
+            path: "output/quota.json".to_string(),
+            daily_limit: 10_000,
+            reserve: 200,
+            warn_at: vec![80, 90, 95],
+        }
+    }
+}
+
+fn token_key(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .take(8)
+        .map(|v| format!("{v:02x}"))
+        .collect()
+}
+
+impl CallBudget {
+    fn load(&self) -> Result<HashMap<String, QuotaRecord>> {
+        QuotaFileStore::load(&self.path).with_context(|| {
+            format!(
+                "Failed to read quota file {}. Fix or delete it to reset the daily count",
+                self.path
+            )
+        })
+    }
+
+    fn today_record(&self, records: &HashMap<String, QuotaRecord>, key: &str) -> QuotaRecord {
+        let today = Utc::now().date_naive();
+
+        match records.get(key) {
+            Some(record) if record.date == today => record.clone(),
+            _ => QuotaRecord { date: today, count: 0 },
+        }
+    }
+
+    fn crossed_thresholds(&self, before: u32, after: u32) -> Vec<u8> {
+        self.warn_at
+            .iter()
+            .copied()
+            .filter(|percent| {
+                let threshold = self.daily_limit * u32::from(*percent) / 100;
+                before < threshold && after >= threshold
+            })
+            .collect()
+    }
+
+    // Blocks on the quota file lock, so async callers should run it on a blocking thread
+    pub fn consume(&self, token: &str, essential: bool) -> Result<()> {
+        let key = token_key(token);
+        let _lock = QuotaFileStore::lock(&self.path)
+            .inspect_err(|e| tracing::warn!("Failed to lock {}: {e}", self.path))
+            .ok();
+        let mut records = self.load()?;
+        let mut record = self.today_record(&records, &key);
+
+        if !essential && self.daily_limit.saturating_sub(record.count) <= self.reserve {
+            return Err(ApiError::BudgetExhausted {
+                used: record.count,
+                limit: self.daily_limit,
+            }
+            .into());
+        }
+
+        let before = record.count;
+        record.count += 1;
+
+        for percent in self.crossed_thresholds(before, record.count) {
+            eprintln!(
+                "warning: {} of {} daily API calls used ({percent}%)",
+                record.count, self.daily_limit
+            );
+        }
+
+        records.insert(key, record);
+        if let Err(e) = QuotaFileStore::save(&self.path, &records) {
+            tracing::warn!("Failed to write {}: {e}", self.path);
+        }
+
+        Ok(())
+    }
+
+    pub fn snapshot(&self, token: &str) -> Result<ApiQuota> {
+        let record = self.today_record(&self.load()?, &token_key(token));
+
+        Ok(ApiQuota {
+            date: record.date,
+            used: record.count,
+            limit: self.daily_limit,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use chrono::Days;
+
+    use super::*;
+
+    static NEXT_FILE: AtomicUsize = AtomicUsize::new(0);
+
+    struct TempBudget(CallBudget);
+
+    impl TempBudget {
+        fn new(daily_limit: u32, reserve: u32) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "switchbot-quota-test-{}-{}/quota.json",
+                std::process::id(),
+                NEXT_FILE.fetch_add(1, Ordering::SeqCst)
+            ));
+
+            Self(CallBudget {
+                path: path.to_string_lossy().into_owned(),
+                daily_limit,
+                reserve,
+                ..CallBudget::default()
+            })
+        }
+
+        fn set_count(&self, date: chrono::NaiveDate, count: u32) {
+            let records = HashMap::from([(token_key("token"), QuotaRecord { date, count })]);
+            QuotaFileStore::save(&self.0.path, &records).unwrap();
+        }
+    }
+
+    impl Drop for TempBudget {
+        fn drop(&mut self) {
+            if let Some(dir) = std::path::Path::new(&self.0.path).parent() {
+                let _ = std::fs::remove_dir_all(dir);
+            }
+        }
+    }
+
+    #[test]
+    fn counts_calls_per_token() {
+        let budget = TempBudget::new(100, 10);
+
+        budget.0.consume("token", false).unwrap();
+        budget.0.consume("token", true).unwrap();
+        budget.0.consume("other", false).unwrap();
+
+        assert_eq!(budget.0.snapshot("token").unwrap().used, 2);
+        assert_eq!(budget.0.snapshot("other").unwrap().used, 1);
+    }
+
+    #[test]
+    fn reserve_is_kept_for_essential_calls() {
+        let budget = TempBudget::new(100, 10);
+        budget.set_count(Utc::now().date_naive(), 89);
+
+        budget.0.consume("token", false).unwrap();
+        let err = budget.0.consume("token", false).unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&ApiError::BudgetExhausted { used: 90, limit: 100 }));
+
+        budget.0.consume("token", true).unwrap();
+        assert_eq!(budget.0.snapshot("token").unwrap().used, 91);
+    }
+
+    #[test]
+    fn count_resets_on_a_new_utc_day() {
+        let budget = TempBudget::new(100, 10);
+        let yesterday = Utc::now().date_naive().checked_sub_days(Days::new(1)).unwrap();
+        budget.set_count(yesterday, 100);
+
+        budget.0.consume("token", false).unwrap();
+
+        let quota = budget.0.snapshot("token").unwrap();
+        assert_eq!(quota.date, Utc::now().date_naive());
+        assert_eq!(quota.used, 1);
+    }
+
+    #[test]
+    fn warns_once_per_crossed_threshold() {
+        let budget = TempBudget::new(100, 10);
+
+        assert_eq!(budget.0.crossed_thresholds(78, 79), Vec::<u8>::new());
+        assert_eq!(budget.0.crossed_thresholds(79, 80), vec![80]);
+        assert_eq!(budget.0.crossed_thresholds(80, 81), Vec::<u8>::new());
+        assert_eq!(budget.0.crossed_thresholds(89, 95), vec![90, 95]);
+    }
+}
@@ -0,0 +1,266 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{Context, Result, anyhow};
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const CASSETTE_FILE: &str = "cassette.json";
+const REDACTED: &str = "<redacted>";
+const REDACTED_HEADERS: [&str; 4] = ["authorization", "sign", "t", "nonce"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub headers: BTreeMap<String, String>,
+    pub body: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    Record,
+    Replay,
+}
+
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    interactions: Mutex<Vec<(Interaction, bool)>>,
+}
+
+fn load(path: &Path) -> Result<Vec<Interaction>> {
+    let json = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read cassette: {}", path.display()))?;
+    serde_json::from_str(&json).with_context(|| format!("Failed to parse cassette: {}", path.display()))
+}
+
+fn save(path: &Path, interactions: &[&Interaction]) -> Result<()> {
+    let json = serde_json::to_string_pretty(interactions)?;
+
+    let mut file = File::create(path)
+        .with_context(|| format!("Failed to write cassette: {}", path.display()))?;
+    file.write_all(json.as_bytes())?;
+
+    Ok(())
+}
+
+impl Cassette {
+    pub fn recorder(dir: &str) -> Result<Self> {
+        fs::create_dir_all(dir)?;
+
+        // Start from an empty cassette so re-recording replaces the previous session
+        let path = Path::new(dir).join(CASSETTE_FILE);
+        save(&path, &[])?;
+
+        Ok(Self::new(path, CassetteMode::Record, Vec::new()))
+    }
+
+    pub fn player(dir: &str) -> Result<Self> {
+        let path = Path::new(dir).join(CASSETTE_FILE);
+        let interactions = load(&path)?;
+
+        Ok(Self::new(path, CassetteMode::Replay, interactions))
+    }
+
+    fn new(path: PathBuf, mode: CassetteMode, interactions: Vec<Interaction>) -> Self {
+        Self {
+            path,
+            mode,
+            interactions: Mutex::new(interactions.into_iter().map(|v| (v, false)).collect()),
+        }
+    }
+
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    pub fn record(
+        &self,
+        method: &str,
+        path: &str,
+        headers: &HeaderMap,
+        body: Option<&Value>,
+        response: RecordedResponse,
+    ) -> Result<()> {
+        let headers = headers
+            .iter()
+            .map(|(name, value)| {
+                let value = match REDACTED_HEADERS.contains(&name.as_str()) {
+                    true => REDACTED.to_string(),
+                    false => value.to_str().unwrap_or_default().to_string(),
+                };
+                (name.to_string(), value)
+            })
+            .collect();
+
+        let interaction = Interaction {
+            request: RecordedRequest {
+                method: method.to_string(),
+                path: path.to_string(),
+                headers,
+                body: body.cloned(),
+            },
+            response,
+        };
+
+        let mut interactions = self.interactions.lock().unwrap();
+        interactions.push((interaction, true));
+
+        let recorded: Vec<&Interaction> = interactions.iter().map(|(v, _)| v).collect();
+        save(&self.path, &recorded)
+    }
+
+    pub fn play(&self, method: &str, path: &str, body: Option<&Value>) -> Result<RecordedResponse> {
+        let mut interactions = self.interactions.lock().unwrap();
+
+        let (interaction, used) = interactions
+            .iter_mut()
+            .find(|(v, used)| {
+                !used
+                    && v.request.method == method
+                    && v.request.path == path
+                    && v.request.body.as_ref() == body
+            })
+            .ok_or_else(|| {
+                anyhow!(
+                    "No recorded response in {} for {method} {path} {}",
+                    self.path.display(),
+                    body.map(Value::to_string).unwrap_or_default()
+                )
+            })?;
+
+        *used = true;
+        Ok(interaction.response.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use reqwest::header::HeaderValue;
+    use serde_json::json;
+
+    use super::*;
+
+    static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            Self(std::env::temp_dir().join(format!(
+                "switchbot-cassette-test-{}-{}",
+                std::process::id(),
+                NEXT_DIR.fetch_add(1, Ordering::SeqCst)
+            )))
+        }
+
+        fn path(&self) -> &str {
+            self.0.to_str().unwrap()
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn response(body: &str) -> RecordedResponse {
+        RecordedResponse { status: 200, body: body.to_string() }
+    }
+
+    fn record_list(cassette: &Cassette, body: &str) {
+        cassette
+            .record("GET", "/devices", &HeaderMap::new(), None, response(body))
+            .unwrap();
+    }
+
+    #[test]
+    fn record_redacts_auth_headers() {
+        let dir = TempDir::new();
+        let cassette = Cassette::recorder(dir.path()).unwrap();
+        let mut headers = HeaderMap::new();
+        for name in REDACTED_HEADERS {
+            headers.insert(name, HeaderValue::from_static("secret"));
+        }
+        headers.insert("content-type", HeaderValue::from_static("application/json"));
+
+        cassette
+            .record("GET", "/devices", &headers, None, response("{}"))
+            .unwrap();
+
+        let recorded = load(&dir.0.join(CASSETTE_FILE)).unwrap();
+        let headers = &recorded[0].request.headers;
+        for name in REDACTED_HEADERS {
+            assert_eq!(headers[name], REDACTED);
+        }
+        assert_eq!(headers["content-type"], "application/json");
+    }
+
+    #[test]
+    fn recording_again_replaces_the_previous_cassette() {
+        let dir = TempDir::new();
+        record_list(&Cassette::recorder(dir.path()).unwrap(), "first");
+
+        let cassette = Cassette::recorder(dir.path()).unwrap();
+        assert!(load(&dir.0.join(CASSETTE_FILE)).unwrap().is_empty());
+        record_list(&cassette, "second");
+
+        let recorded = load(&dir.0.join(CASSETTE_FILE)).unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].response.body, "second");
+    }
+
+    #[test]
+    fn play_serves_each_recorded_response_once() {
+        let dir = TempDir::new();
+        let recorder = Cassette::recorder(dir.path()).unwrap();
+        record_list(&recorder, "first");
+        record_list(&recorder, "second");
+
+        let player = Cassette::player(dir.path()).unwrap();
+
+        assert_eq!(player.play("GET", "/devices", None).unwrap().body, "first");
+        assert_eq!(player.play("GET", "/devices", None).unwrap().body, "second");
+        assert!(player.play("GET", "/devices", None).is_err());
+    }
+
+    #[test]
+    fn play_fails_on_unmatched_request() {
+        let dir = TempDir::new();
+        let recorder = Cassette::recorder(dir.path()).unwrap();
+        let body = json!({ "command": "turnOn" });
+        recorder
+            .record("POST", "/devices/A1/commands", &HeaderMap::new(), Some(&body), response("{}"))
+            .unwrap();
+
+        let player = Cassette::player(dir.path()).unwrap();
+        let other = json!({ "command": "turnOff" });
+
+        let err = player.play("POST", "/devices/A1/commands", Some(&other)).unwrap_err();
+        assert!(err.to_string().contains("No recorded response"));
+        assert!(err.to_string().contains("turnOff"));
+        assert!(player.play("GET", "/devices/A1/commands", Some(&body)).is_err());
+        assert!(player.play("POST", "/devices/A1/commands", Some(&body)).is_ok());
+    }
+}
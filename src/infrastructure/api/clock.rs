@@ -0,0 +1,17 @@
+use std::fmt::Debug;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+
+pub trait IClock: Debug + Send + Sync {
+    fn now_millis(&self) -> Result<u128>;
+}
+
+#[derive(Debug, Default)]
+pub struct SystemClock;
+
+impl IClock for SystemClock {
+    fn now_millis(&self) -> Result<u128> {
+        Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis())
+    }
+}
@@ -0,0 +1,22 @@
+use std::fmt::Debug;
+
+use rand::Rng;
+use rand::distributions::Alphanumeric;
+
+const NONCE_LENGTH: usize = 16;
+
+pub trait INonceGenerator: Debug + Send + Sync {
+    fn generate(&self) -> String;
+}
+
+#[derive(Debug, Default)]
+pub struct RandomNonceGenerator;
+
+impl INonceGenerator for RandomNonceGenerator {
+    fn generate(&self) -> String {
+        rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(NONCE_LENGTH)
+            .collect()
+    }
+}
@@ -0,0 +1,219 @@
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use rand::Rng;
+use strum::{Display, EnumString};
+
+use crate::domain::errors::ApiError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+pub enum RetryOn {
+    #[strum(serialize = "server_error")]
+    ServerError,
+    #[strum(serialize = "timeout")]
+    Timeout,
+    #[strum(serialize = "connect")]
+    Connect,
+    #[strum(serialize = "busy")]
+    Busy,
+}
+
+impl RetryOn {
+    pub fn parse_list(raw: &str) -> Result<Vec<Self>> {
+        raw.split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(|v| v.parse::<Self>().map_err(|_| anyhow!("Unknown retry condition: {v}")))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub retry_on: Vec<RetryOn>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+            retry_on: vec![RetryOn::ServerError, RetryOn::Timeout, RetryOn::Connect, RetryOn::Busy],
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    // Non-idempotent requests are only retried when the API definitely did not apply them:
+    // the connection was never made or the device rejected the command as busy
+    pub fn should_retry(&self, err: &anyhow::Error, idempotent: bool) -> bool {
+        if let Some(e) = err.downcast_ref::<ApiError>() {
+            return match e {
+                ApiError::Http { status } if *status >= 500 => {
+                    idempotent && self.retry_on.contains(&RetryOn::ServerError)
+                }
+                ApiError::DeviceBusy { .. } => self.retry_on.contains(&RetryOn::Busy),
+                _ => false,
+            };
+        }
+
+        if let Some(e) = err.downcast_ref::<reqwest::Error>() {
+            return (e.is_connect() && self.retry_on.contains(&RetryOn::Connect))
+                || (idempotent && e.is_timeout() && self.retry_on.contains(&RetryOn::Timeout));
+        }
+
+        false
+    }
+
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let exp = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay);
+
+        let half = exp.as_millis() as u64 / 2;
+        let jitter = match half {
+            0 => 0,
+            _ => rand::thread_rng().gen_range(0, half + 1),
+        };
+
+        Duration::from_millis(half + jitter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    fn policy(retry_on: &[RetryOn]) -> RetryPolicy {
+        RetryPolicy {
+            retry_on: retry_on.to_vec(),
+            ..RetryPolicy::default()
+        }
+    }
+
+    async fn connect_error() -> anyhow::Error {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        reqwest::Client::builder()
+            .no_proxy()
+            .build()
+            .unwrap()
+            .get(format!("http://{addr}"))
+            .send()
+            .await
+            .unwrap_err()
+            .into()
+    }
+
+    async fn timeout_error() -> anyhow::Error {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let err = reqwest::Client::builder()
+            .timeout(Duration::from_millis(50))
+            .no_proxy()
+            .build()
+            .unwrap()
+            .get(format!("http://{addr}"))
+            .send()
+            .await
+            .unwrap_err();
+        drop(listener);
+        err.into()
+    }
+
+    #[tokio::test]
+    async fn retries_by_condition_and_method() {
+        let server_error: anyhow::Error = ApiError::Http { status: 503 }.into();
+        let busy: anyhow::Error = ApiError::DeviceBusy { message: "busy".into() }.into();
+        let connect = connect_error().await;
+        let timeout = timeout_error().await;
+        assert!(!timeout.downcast_ref::<reqwest::Error>().unwrap().is_connect());
+
+        // (error, condition, retried for GET, retried for POST)
+        let cases = [
+            (&server_error, RetryOn::ServerError, true, false),
+            (&busy, RetryOn::Busy, true, true),
+            (&connect, RetryOn::Connect, true, true),
+            (&timeout, RetryOn::Timeout, true, false),
+        ];
+
+        for (err, condition, get, post) in cases {
+            assert_eq!(policy(&[condition]).should_retry(err, true), get, "GET {condition}");
+            assert_eq!(policy(&[condition]).should_retry(err, false), post, "POST {condition}");
+            assert!(!policy(&[]).should_retry(err, true), "disabled {condition}");
+        }
+    }
+
+    #[test]
+    fn other_errors_are_not_retried() {
+        let policy = RetryPolicy::default();
+
+        for err in [
+            ApiError::Http { status: 404 },
+            ApiError::Unauthorized,
+            ApiError::RateLimited,
+            ApiError::DeviceOffline { message: "offline".into() },
+        ] {
+            assert!(!policy.should_retry(&err.into(), true));
+        }
+        assert!(!policy.should_retry(&anyhow!("parse error"), true));
+    }
+
+    #[test]
+    fn delay_grows_exponentially_with_jitter_and_is_capped() {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1000),
+            ..RetryPolicy::default()
+        };
+
+        // Equal jitter keeps each delay between half and all of the exponential step
+        for (attempt, step) in [(1, 100), (2, 200), (3, 400), (4, 800), (5, 1000), (30, 1000)] {
+            for _ in 0..50 {
+                let delay = policy.delay_for(attempt).as_millis() as u64;
+                assert!((step / 2..=step).contains(&delay), "attempt {attempt}: {delay}ms");
+            }
+        }
+    }
+
+    #[test]
+    fn zero_base_delay_never_sleeps() {
+        let policy = RetryPolicy {
+            base_delay: Duration::ZERO,
+            ..RetryPolicy::default()
+        };
+
+        assert_eq!(policy.delay_for(3), Duration::ZERO);
+    }
+
+    #[test]
+    fn parse_list_reads_known_conditions() {
+        assert_eq!(
+            RetryOn::parse_list("server_error, busy,").unwrap(),
+            vec![RetryOn::ServerError, RetryOn::Busy]
+        );
+        assert!(RetryOn::parse_list("").unwrap().is_empty());
+
+        let err = RetryOn::parse_list("timeout,flaky").unwrap_err();
+        assert!(err.to_string().contains("flaky"));
+    }
+}
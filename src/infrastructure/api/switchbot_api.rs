@@ -1,24 +1,38 @@
 use std::fmt::format;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::{Result, bail};
 use async_trait::async_trait;
 use base64::Engine as _;
 use base64::engine::general_purpose;
 use hmac::{Hmac, Mac};
-use rand::Rng;
-use rand::distributions::Alphanumeric;
-use reqwest::Client;
-use serde_json::Value;
+use reqwest::{Client, Method};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
 use sha2::Sha256;
 
 mod dto;
-use dto::{CommandResponse, DeviceListResponse};
+use dto::{CommandResponse, DeviceListResponse, SceneExecuteResponse, SceneListResponse, SwitchbotApiResponse};
+use dto::{
+    DeleteWebhookRequestBody, QueryWebhookDetailsResponse, QueryWebhookRequestBody,
+    QueryWebhookUrlResponse, SetupWebhookRequestBody, UpdateWebhookRequestBody,
+    WebhookConfigDto, WebhookResponse,
+};
 
-use crate::domain::models::entities::Device;
-use crate::domain::models::value_objects::Command;
-use crate::domain::models::value_objects::DeviceId;
-use crate::domain::repositories::IDeviceRepository;
+mod status_dto;
+use status_dto::DeviceStatusResponse;
+
+use crate::domain::errors::{ApiError, STATUS_SUCCESS};
+use crate::domain::models::entities::{ApiQuota, Device, DeviceStatus, Scene, Webhook};
+use crate::domain::models::value_objects::{Command, HumidifierMode};
+use crate::domain::models::value_objects::{DeviceId, SceneId};
+use crate::domain::repositories::{
+    IDeviceRepository, IQuotaRepository, ISceneRepository, IWebhookRepository,
+};
+use crate::infrastructure::api::{
+    CallBudget, Cassette, CassetteMode, HttpSettings, IClock, INonceGenerator,
+    RandomNonceGenerator, RecordedResponse, RetryPolicy, SystemClock,
+};
 use crate::infrastructure::api::switchbot_api::dto::CommandRequestBody;
 
 #[derive(Debug)]
@@ -27,29 +41,75 @@ pub struct SwitchBotApi {
     pub token: String,
     pub secret: String,
     pub client: Client,
+    pub retry_policy: RetryPolicy,
+    pub call_budget: Option<CallBudget>,
+    cassette: Option<Cassette>,
+    dry_run: bool,
+    clock: Box<dyn IClock>,
+    nonce_generator: Box<dyn INonceGenerator>,
 }
 
 impl SwitchBotApi {
-    pub fn new(host: String, token: String, secret: String) -> Self {
-        Self {
+    pub fn new(host: String, token: String, secret: String, settings: &HttpSettings) -> Result<Self> {
+        Ok(Self {
             host,
             token,
             secret,
-            client: Client::new(),
+            client: settings.build_client()?,
+            retry_policy: RetryPolicy::default(),
+            call_budget: None,
+            cassette: None,
+            dry_run: false,
+            clock: Box::new(SystemClock),
+            nonce_generator: Box::new(RandomNonceGenerator),
+        })
+    }
+
+    pub fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        Self { retry_policy, ..self }
+    }
+
+    pub fn with_call_budget(self, call_budget: CallBudget) -> Self {
+        Self {
+            call_budget: Some(call_budget),
+            ..self
         }
     }
 
-    fn generate_signature(&self) -> Result<(String, String, String)> {
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)?
-            .as_millis()
-            .to_string();
+    pub fn with_cassette(self, cassette: Cassette) -> Self {
+        Self {
+            cassette: Some(cassette),
+            ..self
+        }
+    }
+
+    pub fn with_dry_run(self, dry_run: bool) -> Self {
+        Self { dry_run, ..self }
+    }
+
+    fn is_replaying(&self) -> bool {
+        self.cassette
+            .as_ref()
+            .is_some_and(|v| v.mode() == CassetteMode::Replay)
+    }
+
+    pub fn with_clock(self, clock: impl IClock + 'static) -> Self {
+        Self {
+            clock: Box::new(clock),
+            ..self
+        }
+    }
+
+    pub fn with_nonce_generator(self, nonce_generator: impl INonceGenerator + 'static) -> Self {
+        Self {
+            nonce_generator: Box::new(nonce_generator),
+            ..self
+        }
+    }
 
-        let nonce: String = rand::thread_rng()
-            .sample_iter(&Alphanumeric)
-            .take(16)
-            .map(char::from)
-            .collect();
+    fn generate_signature(&self) -> Result<(String, String, String)> {
+        let timestamp = self.clock.now_millis()?.to_string();
+        let nonce = self.nonce_generator.generate();
 
         let payload = format!("{}{}{}", self.token, &timestamp, &nonce);
 
@@ -89,107 +149,645 @@ impl SwitchBotApi {
 
         devices
     }
+
+    async fn get<T: DeserializeOwned + Default>(&self, path: &str) -> Result<SwitchbotApiResponse<T>> {
+        self.request(Method::GET, path, None, false).await
+    }
+
+    async fn post<B: Serialize, T: DeserializeOwned + Default>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<SwitchbotApiResponse<T>> {
+        self.post_with(path, body, true).await
+    }
+
+    async fn post_query<B: Serialize, T: DeserializeOwned + Default>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<SwitchbotApiResponse<T>> {
+        self.post_with(path, body, false).await
+    }
+
+    async fn post_with<B: Serialize, T: DeserializeOwned + Default>(
+        &self,
+        path: &str,
+        body: &B,
+        essential: bool,
+    ) -> Result<SwitchbotApiResponse<T>> {
+        let body = serde_json::to_value(body)?;
+        self.request(Method::POST, path, Some(&body), essential).await
+    }
+
+    fn print_dry_run(&self, method: &Method, path: &str, body: Option<&Value>) -> Result<()> {
+        use reqwest::header::AUTHORIZATION;
+
+        println!("{method} {}{path}", self.host);
+        for (name, value) in self.auth_headers()?.iter() {
+            match name == AUTHORIZATION || name == "sign" {
+                true => println!("{name}: <redacted>"),
+                false => println!("{name}: {}", value.to_str()?),
+            }
+        }
+        if let Some(body) = body {
+            println!("\n{}", serde_json::to_string_pretty(body)?);
+        }
+
+        Ok(())
+    }
+
+    async fn request<T: DeserializeOwned + Default>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+        essential: bool,
+    ) -> Result<SwitchbotApiResponse<T>> {
+        if self.dry_run {
+            self.print_dry_run(&method, path, body)?;
+            return Ok(SwitchbotApiResponse {
+                status_code: STATUS_SUCCESS,
+                message: "dry run".into(),
+                body: T::default(),
+            });
+        }
+
+        let url = self.host.clone() + path;
+        let max_attempts = self.retry_policy.max_attempts.max(1);
+        let idempotent = method == Method::GET;
+
+        for attempt in 1.. {
+            tracing::debug!("{method} {url:?} (attempt {attempt}/{max_attempts})");
+
+            if let Some(budget) = &self.call_budget
+                && !self.is_replaying()
+            {
+                let (budget, token) = (budget.clone(), self.token.clone());
+                tokio::task::spawn_blocking(move || budget.consume(&token, essential)).await??;
+            }
+
+            match self.send(method.clone(), path, body).await {
+                Err(e) if attempt < max_attempts && self.retry_policy.should_retry(&e, idempotent) => {
+                    let delay = self.retry_policy.delay_for(attempt);
+                    tracing::warn!(
+                        "{method} {url} failed (attempt {attempt}/{max_attempts}): {e}. Retrying in {delay:?}"
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+
+        unreachable!()
+    }
+
+    async fn transport(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<RecordedResponse> {
+        if let Some(cassette) = &self.cassette
+            && cassette.mode() == CassetteMode::Replay
+        {
+            return cassette.play(method.as_str(), path, body);
+        }
+
+        let headers = self.auth_headers()?;
+        let mut req = self
+            .client
+            .request(method.clone(), self.host.clone() + path)
+            .headers(headers.clone());
+
+        if let Some(body) = body {
+            req = req.json(body);
+        }
+
+        let res = req.send().await?;
+        let response = RecordedResponse {
+            status: res.status().as_u16(),
+            body: res.text().await?,
+        };
+
+        if let Some(cassette) = &self.cassette {
+            cassette.record(method.as_str(), path, &headers, body, response.clone())?;
+        }
+
+        Ok(response)
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<SwitchbotApiResponse<T>> {
+        let res = self.transport(method, path, body).await?;
+
+        if !(200..300).contains(&res.status) {
+            return Err(ApiError::from_http_status(res.status).into());
+        }
+
+        let res: SwitchbotApiResponse<Value> = serde_json::from_str(&res.body)?;
+        tracing::debug!("{} {}", res.status_code, res.message);
+
+        if res.status_code != STATUS_SUCCESS {
+            return Err(ApiError::from_status_code(res.status_code, res.message).into());
+        }
+
+        Ok(SwitchbotApiResponse {
+            status_code: res.status_code,
+            message: res.message,
+            body: serde_json::from_value(res.body)?,
+        })
+    }
 }
 
 #[async_trait]
 impl IDeviceRepository for SwitchBotApi {
     async fn get_device(&self, id: &DeviceId) -> Result<Device> {
-        // 仮実装
-        Ok(Device {
-            id: id.clone(),
-            name: "dummy".to_string(),
-            device_type: "Bot".to_string(),
-            is_infrared: false,
-            hub_device_id: "hoge".to_string(),
-        })
+        self.get_device_list()
+            .await?
+            .into_iter()
+            .find(|device| &device.id == id)
+            .ok_or_else(|| {
+                ApiError::DeviceNotFound {
+                    message: id.value().unwrap_or_default(),
+                }
+                .into()
+            })
+    }
+
+    async fn get_device_status(&self, id: &DeviceId) -> Result<DeviceStatus> {
+        let path = "/devices/".to_string() + &id.value()? + "/status";
+
+        let res: DeviceStatusResponse = self.get(&path).await?;
+        tracing::debug!("{res:?}");
+
+        DeviceStatus::try_from(res.body)
     }
 
     async fn send_command(&self, id: &DeviceId, command: &Command) -> Result<()> {
-        let url = self.host.clone() + "/devices/" + &id.value()?.to_string() + "/commands";
-        tracing::debug!("{:?}", url);
+        let path = "/devices/".to_string() + &id.value()? + "/commands";
 
-        let body = match command {
-            Command::TurnOn => CommandRequestBody {
-                command_type: "command".into(),
-                command: "turnOn".into(),
-                parameter: "default".into(),
-            },
-            Command::TurnOff => CommandRequestBody {
-                command_type: "command".into(),
-                command: "turnOff".into(),
-                parameter: "default".into(),
-            },
-            Command::SetBrightness(value) => CommandRequestBody {
+        let body = command_request_body(command);
+
+        let res: CommandResponse = self.post(&path, &body).await?;
+        tracing::debug!("{res:?}");
+        Ok(())
+    }
+
+    async fn get_device_list(&self) -> Result<Vec<Device>> {
+        let res: DeviceListResponse = self.get("/devices").await?;
+        let devices = self.to_device_list(res);
+
+        Ok(devices)
+    }
+}
+
+fn command_request_body(command: &Command) -> CommandRequestBody {
+    match command {
+        Command::TurnOn => CommandRequestBody {
+            command_type: "command".into(),
+            command: "turnOn".into(),
+            parameter: "default".into(),
+        },
+        Command::TurnOff => CommandRequestBody {
+            command_type: "command".into(),
+            command: "turnOff".into(),
+            parameter: "default".into(),
+        },
+        Command::SetBrightness(value) => CommandRequestBody {
+            command_type: "command".into(),
+            command: "setBrightness".into(),
+            parameter: Value::Number(value.get().into()),
+        },
+        Command::SetColor(values) => {
+            let (r, g, b) = values.get();
+            CommandRequestBody {
                 command_type: "command".into(),
-                command: "setBrightness".into(),
-                parameter: Value::Number(value.get().into()),
-            },
-            Command::SetColor(values) => {
-                let (r, g, b) = values.get();
-                CommandRequestBody {
-                    command_type: "command".into(),
-                    command: "setColor".into(),
-                    parameter: Value::String(format!("{}:{}:{}", r, g, b)),
-                }
+                command: "setColor".into(),
+                parameter: Value::String(format!("{}:{}:{}", r, g, b)),
             }
-            Command::SetColorTemperature(value) => CommandRequestBody {
-                command_type: "command".into(),
-                command: "setColorTemperature".into(),
-                parameter: Value::Number(value.get().into()),
-            },
-            Command::AcSetAll(values) => CommandRequestBody {
-                command_type: "command".into(),
-                command: "setAll".into(),
-                parameter: Value::String(format!(
-                    "{},{},{},{}", 
-                    values.temperature,
-                    values.mode as u8,
-                    values.fan_speed as u8,
-                    values.power_state.to_string(),
-                )),
-            },
-            Command::Custom { command_type, command, parameter} => CommandRequestBody { 
-                command_type: command_type.clone(), 
-                command: command.clone(),
-                parameter: Value::String(parameter.clone())
-            },
+        }
+        Command::SetColorTemperature(value) => CommandRequestBody {
+            command_type: "command".into(),
+            command: "setColorTemperature".into(),
+            parameter: Value::Number(value.get().into()),
+        },
+        Command::AcSetAll(values) => CommandRequestBody {
+            command_type: "command".into(),
+            command: "setAll".into(),
+            parameter: Value::String(format!(
+                "{},{},{},{}", 
+                values.temperature,
+                values.mode as u8,
+                values.fan_speed as u8,
+                values.power_state,
+            )),
+        },
+        Command::CurtainSetPosition(values) => CommandRequestBody {
+            command_type: "command".into(),
+            command: "setPosition".into(),
+            parameter: Value::String(format!(
+                "{},{},{}",
+                values.index,
+                values.mode,
+                values.position.get(),
+            )),
+        },
+        Command::BlindTiltSetPosition(values) => CommandRequestBody {
+            command_type: "command".into(),
+            command: "setPosition".into(),
+            parameter: Value::String(format!(
+                "{};{}",
+                values.direction,
+                values.angle.get(),
+            )),
+        },
+        Command::Lock => CommandRequestBody {
+            command_type: "command".into(),
+            command: "lock".into(),
+            parameter: "default".into(),
+        },
+        Command::Unlock => CommandRequestBody {
+            command_type: "command".into(),
+            command: "unlock".into(),
+            parameter: "default".into(),
+        },
+        Command::Deadbolt => CommandRequestBody {
+            command_type: "command".into(),
+            command: "deadbolt".into(),
+            parameter: "default".into(),
+        },
+        Command::Press => CommandRequestBody {
+            command_type: "command".into(),
+            command: "press".into(),
+            parameter: "default".into(),
+        },
+        Command::VacuumStart => CommandRequestBody {
+            command_type: "command".into(),
+            command: "start".into(),
+            parameter: "default".into(),
+        },
+        Command::VacuumStop => CommandRequestBody {
+            command_type: "command".into(),
+            command: "stop".into(),
+            parameter: "default".into(),
+        },
+        Command::VacuumDock => CommandRequestBody {
+            command_type: "command".into(),
+            command: "dock".into(),
+            parameter: "default".into(),
+        },
+        Command::VacuumPowerLevel(value) => CommandRequestBody {
+            command_type: "command".into(),
+            command: "PowerLevel".into(),
+            parameter: Value::Number(value.get().into()),
+        },
+        Command::VacuumStartClean(values) => CommandRequestBody {
+            command_type: "command".into(),
+            command: "startClean".into(),
+            parameter: json!({
+                "action": values.action.to_string(),
+                "param": {
+                    "fanLevel": values.fan_level.get(),
+                    "waterLevel": values.water_level.get(),
+                    "times": values.times.get(),
+                },
+            }),
+        },
+        Command::HumidifierSetMode(mode) => CommandRequestBody {
+            command_type: "command".into(),
+            command: "setMode".into(),
+            parameter: Value::String(match mode {
+                HumidifierMode::AUTO => "auto".into(),
+                HumidifierMode::LOW => "101".into(),
+                HumidifierMode::MEDIUM => "102".into(),
+                HumidifierMode::HIGH => "103".into(),
+                HumidifierMode::TARGET(value) => value.get().to_string(),
+            }),
+        },
+        Command::EvaporativeHumidifierSetMode(values) => CommandRequestBody {
+            command_type: "command".into(),
+            command: "setMode".into(),
+            parameter: json!({
+                "mode": values.mode as u8,
+                "targetHumidify": values.target_humidity.get(),
+            }),
+        },
+        Command::SetChildLock(enable) => CommandRequestBody {
+            command_type: "command".into(),
+            command: "setChildLock".into(),
+            parameter: Value::Bool(*enable),
+        },
+        Command::SetChannel(value) => CommandRequestBody {
+            command_type: "command".into(),
+            command: "SetChannel".into(),
+            parameter: Value::String(value.get().to_string()),
+        },
+        Command::VolumeAdd => CommandRequestBody {
+            command_type: "command".into(),
+            command: "volumeAdd".into(),
+            parameter: "default".into(),
+        },
+        Command::VolumeSub => CommandRequestBody {
+            command_type: "command".into(),
+            command: "volumeSub".into(),
+            parameter: "default".into(),
+        },
+        Command::ChannelAdd => CommandRequestBody {
+            command_type: "command".into(),
+            command: "channelAdd".into(),
+            parameter: "default".into(),
+        },
+        Command::ChannelSub => CommandRequestBody {
+            command_type: "command".into(),
+            command: "channelSub".into(),
+            parameter: "default".into(),
+        },
+        Command::SetMute => CommandRequestBody {
+            command_type: "command".into(),
+            command: "setMute".into(),
+            parameter: "default".into(),
+        },
+        Command::IrFan(action) => CommandRequestBody {
+            command_type: "command".into(),
+            command: action.to_string(),
+            parameter: "default".into(),
+        },
+        Command::IrLight(action) => CommandRequestBody {
+            command_type: "command".into(),
+            command: action.to_string(),
+            parameter: "default".into(),
+        },
+        Command::Custom { command_type, command, parameter} => CommandRequestBody { 
+            command_type: command_type.clone(), 
+            command: command.clone(),
+            parameter: parameter.clone(),
+        },
+    }
+}
+
+#[async_trait]
+impl ISceneRepository for SwitchBotApi {
+    async fn get_scene_list(&self) -> Result<Vec<Scene>> {
+        let res: SceneListResponse = self.get("/scenes").await?;
+        let scenes = res.body.into_iter().map(Scene::from).collect();
+
+        Ok(scenes)
+    }
+
+    async fn execute_scene(&self, id: &SceneId) -> Result<()> {
+        let path = "/scenes/".to_string() + &id.value()? + "/execute";
+
+        let res: SceneExecuteResponse = self.post(&path, &Value::Object(Default::default())).await?;
+        tracing::debug!("{res:?}");
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl IWebhookRepository for SwitchBotApi {
+    async fn setup_webhook(&self, url: &str) -> Result<()> {
+        let body = SetupWebhookRequestBody {
+            action: "setupWebhook".into(),
+            url: url.into(),
+            device_list: "ALL".into(),
         };
 
-        let req = self
-            .client
-            .post(&url)
-            .headers(self.auth_headers()?)
-            .json(&body);
+        let res: WebhookResponse = self.post("/webhook/setupWebhook", &body).await?;
+        tracing::debug!("{res:?}");
+        Ok(())
+    }
 
-        let res = req.send().await?;
+    async fn query_webhook_urls(&self) -> Result<Vec<String>> {
+        let body = QueryWebhookRequestBody {
+            action: "queryUrl".into(),
+            urls: None,
+        };
 
-        if !res.status().is_success() {
-            bail!("API Error: {}", res.status())
-        }
+        let res: QueryWebhookUrlResponse = self.post_query("/webhook/queryWebhook", &body).await?;
+        tracing::debug!("{res:?}");
+        Ok(res.body.urls)
+    }
 
-        let res: CommandResponse = res.json().await?;
+    async fn query_webhook_details(&self, urls: &[String]) -> Result<Vec<Webhook>> {
+        let body = QueryWebhookRequestBody {
+            action: "queryDetails".into(),
+            urls: Some(urls.to_vec()),
+        };
+
+        let res: QueryWebhookDetailsResponse = self.post_query("/webhook/queryWebhook", &body).await?;
+        tracing::debug!("{res:?}");
+        Ok(res.body.into_iter().map(Webhook::from).collect())
+    }
+
+    async fn update_webhook(&self, url: &str, enable: bool) -> Result<()> {
+        let body = UpdateWebhookRequestBody {
+            action: "updateWebhook".into(),
+            config: WebhookConfigDto {
+                url: url.into(),
+                enable,
+            },
+        };
+
+        let res: WebhookResponse = self.post("/webhook/updateWebhook", &body).await?;
         tracing::debug!("{res:?}");
         Ok(())
     }
 
-    async fn get_device_list(&self) -> Result<Vec<Device>> {
-        let url = self.host.clone() + "/devices";
-        tracing::debug!("{:?}", url);
+    async fn delete_webhook(&self, url: &str) -> Result<()> {
+        let body = DeleteWebhookRequestBody {
+            action: "deleteWebhook".into(),
+            url: url.into(),
+        };
 
-        let req = self
-            .client
-            .get(url)
-            .headers(self.auth_headers()?);
+        let res: WebhookResponse = self.post("/webhook/deleteWebhook", &body).await?;
+        tracing::debug!("{res:?}");
+        Ok(())
+    }
+}
 
-        let res = req.send().await?;
+#[async_trait]
+impl IQuotaRepository for SwitchBotApi {
+    async fn get_quota(&self) -> Result<ApiQuota> {
+        match &self.call_budget {
+            Some(budget) => budget.snapshot(&self.token),
+            None => bail!("API call tracking is disabled"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        if !res.status().is_success() {
-            bail!("Request failed with status: {}", res.status())
+    #[derive(Debug)]
+    struct FixedClock(u128);
+
+    impl IClock for FixedClock {
+        fn now_millis(&self) -> Result<u128> {
+            Ok(self.0)
         }
+    }
 
-        let res: DeviceListResponse = res.json().await?;
-        let devices = self.to_device_list(res);
+    #[derive(Debug)]
+    struct FixedNonce(&'static str);
 
-        Ok(devices)
+    impl INonceGenerator for FixedNonce {
+        fn generate(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    fn api(token: &str, secret: &str, t: u128, nonce: &'static str) -> SwitchBotApi {
+        let settings = HttpSettings::default();
+        SwitchBotApi::new("http://localhost".to_string(), token.to_string(), secret.to_string(), &settings)
+            .unwrap()
+            .with_clock(FixedClock(t))
+            .with_nonce_generator(FixedNonce(nonce))
+    }
+
+    #[test]
+    fn auth_headers_are_signed_with_fixed_clock_and_nonce() {
+        let headers = api("token", "secret", 1_700_000_000_000, "abcdef0123456789")
+            .auth_headers()
+            .unwrap();
+
+        assert_eq!(headers["authorization"], "token");
+        assert_eq!(headers["t"], "1700000000000");
+        assert_eq!(headers["nonce"], "abcdef0123456789");
+        assert_eq!(headers["sign"], "X34LGSsjXkA14vMYmTj6ik1VQX8ZWc/Gs5sR+hGoD48=");
+        assert_eq!(headers["content-type"], "application/json");
+    }
+
+    #[test]
+    fn auth_headers_sign_other_credentials() {
+        let headers = api("yourToken", "yourSecret", 1_667_369_394_000, "requestID")
+            .auth_headers()
+            .unwrap();
+
+        assert_eq!(headers["t"], "1667369394000");
+        assert_eq!(headers["nonce"], "requestID");
+        assert_eq!(headers["sign"], "8cqKGdLMmo+r05UOwcp8XxtZTh3z5rQj+P+7OLHjPl4=");
+    }
+
+    #[test]
+    fn random_nonce_is_alphanumeric_and_fresh() {
+        let generator = RandomNonceGenerator;
+        let a = generator.generate();
+        let b = generator.generate();
+
+        assert_eq!(a.len(), 16);
+        assert!(a.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert_ne!(a, b);
+    }
+
+    fn body(command: Command) -> Value {
+        serde_json::to_value(command_request_body(&command)).unwrap()
+    }
+
+    #[test]
+    fn curtain_and_blind_tilt_positions_use_their_wire_format() {
+        use crate::domain::models::value_objects::{
+            BlindTiltDirection, BlindTiltPositionValues, CurtainMode, CurtainPositionValues,
+            PositionValue, TiltAngleValue,
+        };
+
+        let curtain = |index, mode, position| {
+            Command::CurtainSetPosition(CurtainPositionValues {
+                index,
+                mode,
+                position: PositionValue::try_from(position).unwrap(),
+            })
+        };
+        let blind = |direction, angle| {
+            Command::BlindTiltSetPosition(BlindTiltPositionValues {
+                direction,
+                angle: TiltAngleValue::try_from(angle).unwrap(),
+            })
+        };
+
+        let cases = [
+            (curtain(0, CurtainMode::PERFORMANCE, 0), "0,0,0"),
+            (curtain(0, CurtainMode::SILENT, 50), "0,1,50"),
+            (curtain(1, CurtainMode::DEFAULT, 100), "1,ff,100"),
+            (blind(BlindTiltDirection::UP, 60), "up;60"),
+            (blind(BlindTiltDirection::DOWN, 0), "down;0"),
+        ];
+
+        for (command, parameter) in cases {
+            assert_eq!(
+                body(command),
+                json!({ "commandType": "command", "command": "setPosition", "parameter": parameter })
+            );
+        }
+    }
+
+    #[test]
+    fn vacuum_start_clean_sends_a_json_object() {
+        use crate::domain::models::value_objects::{
+            VacuumCleanAction, VacuumCleanTimesValue, VacuumCleanValues, VacuumFanLevelValue,
+            VacuumWaterLevelValue,
+        };
+
+        let command = Command::VacuumStartClean(VacuumCleanValues {
+            action: VacuumCleanAction::SWEEP_MOP,
+            fan_level: VacuumFanLevelValue::try_from(2).unwrap(),
+            water_level: VacuumWaterLevelValue::try_from(1).unwrap(),
+            times: VacuumCleanTimesValue::try_from(1).unwrap(),
+        });
+
+        assert_eq!(
+            body(command),
+            json!({
+                "commandType": "command",
+                "command": "startClean",
+                "parameter": { "action": "sweep_mop", "param": { "fanLevel": 2, "waterLevel": 1, "times": 1 } },
+            })
+        );
+    }
+
+    #[test]
+    fn humidifier_commands_use_their_wire_format() {
+        use crate::domain::models::value_objects::{
+            EvaporativeHumidifierMode, EvaporativeHumidifierValues, HumidityValue,
+        };
+
+        let cases = [
+            (
+                Command::HumidifierSetMode(HumidifierMode::MEDIUM),
+                json!({ "commandType": "command", "command": "setMode", "parameter": "102" }),
+            ),
+            (
+                Command::HumidifierSetMode(HumidifierMode::TARGET(HumidityValue::try_from(45).unwrap())),
+                json!({ "commandType": "command", "command": "setMode", "parameter": "45" }),
+            ),
+            (
+                Command::EvaporativeHumidifierSetMode(EvaporativeHumidifierValues {
+                    mode: EvaporativeHumidifierMode::AUTO,
+                    target_humidity: HumidityValue::try_from(45).unwrap(),
+                }),
+                json!({
+                    "commandType": "command",
+                    "command": "setMode",
+                    "parameter": { "mode": 7, "targetHumidify": 45 },
+                }),
+            ),
+            (
+                Command::SetChildLock(true),
+                json!({ "commandType": "command", "command": "setChildLock", "parameter": true }),
+            ),
+            (
+                Command::SetChildLock(false),
+                json!({ "commandType": "command", "command": "setChildLock", "parameter": false }),
+            ),
+        ];
+
+        for (command, expected) in cases {
+            assert_eq!(body(command), expected);
+        }
     }
 }
@@ -1,8 +1,9 @@
+use chrono::DateTime;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::domain::models::entities::Device;
-use crate::domain::models::value_objects::DeviceId;
+use crate::domain::models::entities::{Device, Scene, Webhook};
+use crate::domain::models::value_objects::{DeviceId, DeviceType, SceneId};
 
 #[allow(dead_code)]
 #[derive(Debug, Deserialize)]
@@ -10,12 +11,13 @@ use crate::domain::models::value_objects::DeviceId;
 pub(super) struct SwitchbotApiResponse<T> {
     pub(super) status_code: i32,
     pub(super) message: String,
+    #[serde(default)]
     pub(super) body: T,
 }
 
 pub(super) type DeviceListResponse = SwitchbotApiResponse<DeviceListResponseBody>;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(super) struct DeviceListResponseBody {
     pub(super) device_list: Vec<DeviceDto>,
@@ -35,7 +37,7 @@ impl From<DeviceDto> for Device {
         Device {
             id: DeviceId::new(dto.device_id),
             name: dto.device_name,
-            device_type: dto.device_type,
+            device_type: DeviceType::from(dto.device_type),
             is_infrared: false,
             hub_device_id: dto.hub_device_id,
         }
@@ -55,7 +57,7 @@ impl From<IrRemoteDto> for Device {
         Device {
             id: DeviceId::new(dto.device_id),
             name: dto.device_name,
-            device_type: dto.remote_type,
+            device_type: DeviceType::from(dto.remote_type),
             is_infrared: true,
             hub_device_id: dto.hub_device_id,
         }
@@ -73,3 +75,90 @@ pub(super) struct CommandRequestBody {
 
 pub(super) type CommandResponse = SwitchbotApiResponse<CommandResponseBody>;
 pub(super) type CommandResponseBody = Option<Value>;
+
+pub(super) type SceneListResponse = SwitchbotApiResponse<Vec<SceneDto>>;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct SceneDto {
+    scene_id: String,
+    scene_name: String,
+}
+impl From<SceneDto> for Scene {
+    fn from(dto: SceneDto) -> Self {
+        Scene {
+            id: SceneId::new(dto.scene_id),
+            name: dto.scene_name,
+        }
+    }
+}
+
+pub(super) type SceneExecuteResponse = SwitchbotApiResponse<Option<Value>>;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct SetupWebhookRequestBody {
+    pub(super) action: String,
+    pub(super) url: String,
+    pub(super) device_list: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct QueryWebhookRequestBody {
+    pub(super) action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) urls: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct UpdateWebhookRequestBody {
+    pub(super) action: String,
+    pub(super) config: WebhookConfigDto,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct WebhookConfigDto {
+    pub(super) url: String,
+    pub(super) enable: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct DeleteWebhookRequestBody {
+    pub(super) action: String,
+    pub(super) url: String,
+}
+
+pub(super) type WebhookResponse = SwitchbotApiResponse<Option<Value>>;
+pub(super) type QueryWebhookUrlResponse = SwitchbotApiResponse<WebhookUrlsDto>;
+pub(super) type QueryWebhookDetailsResponse = SwitchbotApiResponse<Vec<WebhookDetailDto>>;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct WebhookUrlsDto {
+    pub(super) urls: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct WebhookDetailDto {
+    url: String,
+    create_time: Option<i64>,
+    last_update_time: Option<i64>,
+    device_list: String,
+    enable: bool,
+}
+impl From<WebhookDetailDto> for Webhook {
+    fn from(dto: WebhookDetailDto) -> Self {
+        Webhook {
+            url: dto.url,
+            device_list: dto.device_list,
+            enable: dto.enable,
+            create_time: dto.create_time.and_then(DateTime::from_timestamp_millis),
+            last_update_time: dto.last_update_time.and_then(DateTime::from_timestamp_millis),
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use anyhow::{Result, anyhow};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::domain::models::entities::DeviceStatus;
+use crate::domain::models::value_objects::{
+    BotMode, DeviceId, DeviceType, DoorState, LockState, PowerState,
+};
+
+use super::dto::SwitchbotApiResponse;
+
+pub(super) type DeviceStatusResponse = SwitchbotApiResponse<DeviceStatusBodyDto>;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct DeviceStatusBodyDto {
+    device_id: String,
+    device_type: String,
+    #[serde(default)]
+    hub_device_id: String,
+    #[serde(flatten)]
+    fields: Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BotStatusDto {
+    power: Option<String>,
+    device_mode: Option<String>,
+    battery: Option<u8>,
+    version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurtainStatusDto {
+    moving: Option<bool>,
+    battery: Option<u8>,
+    version: Option<String>,
+    slide_position: Option<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MeterStatusDto {
+    temperature: Option<f32>,
+    humidity: Option<u8>,
+    battery: Option<u8>,
+    version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PlugStatusDto {
+    power: Option<String>,
+    version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LightStatusDto {
+    power: Option<String>,
+    brightness: Option<u8>,
+    color: Option<String>,
+    color_temperature: Option<u16>,
+    version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LockStatusDto {
+    lock_state: Option<String>,
+    door_state: Option<String>,
+    battery: Option<u8>,
+    version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HumidifierStatusDto {
+    power: Option<String>,
+    humidity: Option<u8>,
+    temperature: Option<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SensorStatusDto {
+    battery: Option<u8>,
+    version: Option<String>,
+}
+
+fn parse_fields<T: DeserializeOwned>(fields: Value) -> Result<T> {
+    serde_json::from_value(fields).map_err(|e| anyhow!("Failed to parse device status: {e}"))
+}
+
+fn parse_power(power: Option<String>) -> Option<PowerState> {
+    power.and_then(|v| v.parse::<PowerState>().ok())
+}
+
+fn parse_color(color: Option<String>) -> Option<(u8, u8, u8)> {
+    let color = color?;
+    let mut rgb = color.split(':').map(|v| v.parse::<u8>().ok());
+    Some((rgb.next()??, rgb.next()??, rgb.next()??))
+}
+
+impl TryFrom<DeviceStatusBodyDto> for DeviceStatus {
+    type Error = anyhow::Error;
+
+    fn try_from(dto: DeviceStatusBodyDto) -> Result<Self> {
+        let mut status = DeviceStatus::new(
+            DeviceId::new(dto.device_id),
+            DeviceType::from(dto.device_type),
+            dto.hub_device_id,
+        );
+
+        match status.device_type {
+            DeviceType::Bot => {
+                let v: BotStatusDto = parse_fields(dto.fields)?;
+                status.power = parse_power(v.power);
+                status.bot_mode = v.device_mode.and_then(|v| v.parse::<BotMode>().ok());
+                status.battery = v.battery;
+                status.version = v.version;
+            }
+            DeviceType::Curtain
+            | DeviceType::Curtain3
+            | DeviceType::BlindTilt
+            | DeviceType::RollerShade => {
+                let v: CurtainStatusDto = parse_fields(dto.fields)?;
+                status.moving = v.moving;
+                status.battery = v.battery;
+                status.version = v.version;
+                status.slide_position = v.slide_position;
+            }
+            DeviceType::Meter
+            | DeviceType::MeterPlus
+            | DeviceType::MeterPro
+            | DeviceType::MeterProCo2
+            | DeviceType::OutdoorMeter
+            | DeviceType::Hub2 => {
+                let v: MeterStatusDto = parse_fields(dto.fields)?;
+                status.temperature = v.temperature;
+                status.humidity = v.humidity;
+                status.battery = v.battery;
+                status.version = v.version;
+            }
+            DeviceType::Plug | DeviceType::PlugMiniUs | DeviceType::PlugMiniJp => {
+                let v: PlugStatusDto = parse_fields(dto.fields)?;
+                status.power = parse_power(v.power);
+                status.version = v.version;
+            }
+            DeviceType::ColorBulb
+            | DeviceType::StripLight
+            | DeviceType::CeilingLight
+            | DeviceType::CeilingLightPro => {
+                let v: LightStatusDto = parse_fields(dto.fields)?;
+                status.power = parse_power(v.power);
+                status.brightness = v.brightness;
+                status.color = parse_color(v.color);
+                status.color_temperature = v.color_temperature;
+                status.version = v.version;
+            }
+            DeviceType::SmartLock | DeviceType::SmartLockPro => {
+                let v: LockStatusDto = parse_fields(dto.fields)?;
+                status.lock_state = v.lock_state.and_then(|v| v.parse::<LockState>().ok());
+                status.door_state = v.door_state.and_then(|v| v.parse::<DoorState>().ok());
+                status.battery = v.battery;
+                status.version = v.version;
+            }
+            DeviceType::Humidifier => {
+                let v: HumidifierStatusDto = parse_fields(dto.fields)?;
+                status.power = parse_power(v.power);
+                status.humidity = v.humidity;
+                status.temperature = v.temperature;
+            }
+            _ => {
+                let v: SensorStatusDto = parse_fields(dto.fields)?;
+                status.battery = v.battery;
+                status.version = v.version;
+            }
+        }
+
+        Ok(status)
+    }
+}
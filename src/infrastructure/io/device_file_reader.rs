@@ -0,0 +1,16 @@
+use std::fs;
+
+use anyhow::{Context, Result};
+
+use crate::domain::models::entities::Device;
+
+pub struct DeviceFileReader;
+
+impl DeviceFileReader {
+    pub fn read_from_json(path: &str) -> Result<Vec<Device>> {
+        let contents = fs::read_to_string(path).with_context(|| format!("Failed to read device file: {}", path))?;
+        let devices: Vec<Device> = serde_json::from_str(&contents).with_context(|| "Failed to parse device JSON")?;
+
+        Ok(devices)
+    }
+}
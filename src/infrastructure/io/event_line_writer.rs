@@ -0,0 +1,36 @@
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::sync::Mutex;
+
+use anyhow::{Result, anyhow};
+
+use crate::domain::models::events::DeviceEvent;
+
+pub struct EventLineWriter {
+    out: Mutex<Box<dyn Write + Send>>,
+}
+
+impl EventLineWriter {
+    pub fn stdout() -> Self {
+        Self {
+            out: Mutex::new(Box::new(io::stdout())),
+        }
+    }
+
+    pub fn file(path: &str) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            out: Mutex::new(Box::new(file)),
+        })
+    }
+
+    pub fn write(&self, event: &DeviceEvent) -> Result<()> {
+        let line = serde_json::to_string(event)?;
+
+        let mut out = self.out.lock().map_err(|e| anyhow!("{e}"))?;
+        writeln!(out, "{line}")?;
+        out.flush()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaRecord {
+    pub date: NaiveDate,
+    pub count: u32,
+}
+
+pub struct QuotaFileStore;
+
+impl QuotaFileStore {
+    pub fn load(path: &str) -> Result<HashMap<String, QuotaRecord>> {
+        if !Path::new(path).exists() {
+            return Ok(HashMap::new());
+        }
+
+        let json = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn save(path: &str, records: &HashMap<String, QuotaRecord>) -> Result<()> {
+        if let Some(dir) = Path::new(path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let json = serde_json::to_string_pretty(records)?;
+
+        let tmp = format!("{path}.tmp");
+        let mut file = File::create(&tmp)?;
+        file.write_all(json.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp, path)?;
+
+        Ok(())
+    }
+
+    pub fn lock(path: &str) -> Result<File> {
+        if let Some(dir) = Path::new(path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(format!("{path}.lock"))?;
+        file.lock()?;
+
+        Ok(file)
+    }
+}
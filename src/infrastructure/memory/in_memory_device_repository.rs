@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::domain::errors::ApiError;
+use crate::domain::models::entities::{Device, DeviceStatus};
+use crate::domain::models::value_objects::{Command, DeviceId, PowerState};
+use crate::domain::repositories::IDeviceRepository;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepositoryCall {
+    GetDevice,
+    GetDeviceStatus,
+    SendCommand,
+    GetDeviceList,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    devices: Vec<Device>,
+    statuses: HashMap<DeviceId, DeviceStatus>,
+    commands: Vec<(DeviceId, Command)>,
+    failures: Vec<(RepositoryCall, ApiError)>,
+}
+
+#[derive(Debug, Default)]
+pub struct InMemoryDeviceRepository {
+    state: Mutex<State>,
+}
+
+impl InMemoryDeviceRepository {
+    pub fn new(devices: Vec<Device>) -> Self {
+        let statuses = devices
+            .iter()
+            .map(|v| {
+                let status = DeviceStatus::new(v.id.clone(), v.device_type.clone(), v.hub_device_id.clone());
+                (v.id.clone(), status)
+            })
+            .collect();
+
+        Self {
+            state: Mutex::new(State {
+                devices,
+                statuses,
+                ..State::default()
+            }),
+        }
+    }
+
+    pub fn with_status(self, status: DeviceStatus) -> Self {
+        self.state.lock().unwrap().statuses.insert(status.id.clone(), status);
+        self
+    }
+
+    pub fn fail_next(&self, call: RepositoryCall, error: ApiError) {
+        self.state.lock().unwrap().failures.push((call, error));
+    }
+
+    pub fn sent_commands(&self) -> Vec<(DeviceId, Command)> {
+        self.state.lock().unwrap().commands.clone()
+    }
+
+    pub fn status(&self, id: &DeviceId) -> Option<DeviceStatus> {
+        self.state.lock().unwrap().statuses.get(id).cloned()
+    }
+
+    fn take_failure(state: &mut State, call: RepositoryCall) -> Result<()> {
+        match state.failures.iter().position(|(v, _)| *v == call) {
+            Some(index) => Err(state.failures.remove(index).1.into()),
+            None => Ok(()),
+        }
+    }
+
+    fn not_found(id: &DeviceId) -> anyhow::Error {
+        ApiError::DeviceNotFound {
+            message: id.value().unwrap_or_default(),
+        }
+        .into()
+    }
+}
+
+fn apply_command(status: &mut DeviceStatus, command: &Command) {
+    match command {
+        Command::TurnOn => status.power = Some(PowerState::On),
+        Command::TurnOff => status.power = Some(PowerState::Off),
+        Command::SetBrightness(value) => status.brightness = Some(value.get()),
+        Command::SetColor(values) => status.color = Some(values.get()),
+        Command::SetColorTemperature(value) => status.color_temperature = Some(value.get()),
+        Command::CurtainSetPosition(values) => status.slide_position = Some(values.position.get()),
+        Command::Lock | Command::Unlock | Command::Deadbolt => {
+            status.lock_state = command.expected_lock_state();
+        }
+        _ => {}
+    }
+}
+
+#[async_trait]
+impl IDeviceRepository for InMemoryDeviceRepository {
+    async fn get_device(&self, id: &DeviceId) -> Result<Device> {
+        let mut state = self.state.lock().unwrap();
+        Self::take_failure(&mut state, RepositoryCall::GetDevice)?;
+
+        state
+            .devices
+            .iter()
+            .find(|v| &v.id == id)
+            .cloned()
+            .ok_or_else(|| Self::not_found(id))
+    }
+
+    async fn get_device_status(&self, id: &DeviceId) -> Result<DeviceStatus> {
+        let mut state = self.state.lock().unwrap();
+        Self::take_failure(&mut state, RepositoryCall::GetDeviceStatus)?;
+
+        state.statuses.get(id).cloned().ok_or_else(|| Self::not_found(id))
+    }
+
+    async fn send_command(&self, id: &DeviceId, command: &Command) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.commands.push((id.clone(), command.clone()));
+        Self::take_failure(&mut state, RepositoryCall::SendCommand)?;
+
+        let status = state.statuses.get_mut(id).ok_or_else(|| Self::not_found(id))?;
+        apply_command(status, command);
+
+        Ok(())
+    }
+
+    async fn get_device_list(&self) -> Result<Vec<Device>> {
+        let mut state = self.state.lock().unwrap();
+        Self::take_failure(&mut state, RepositoryCall::GetDeviceList)?;
+
+        Ok(state.devices.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::value_objects::{BrightnessValue, DeviceType, LockState};
+
+    #[tokio::test]
+    async fn send_command_records_and_updates_status() {
+        let id = DeviceId::new("light".to_string());
+        let repo = InMemoryDeviceRepository::new(vec![Device::new(
+            DeviceId::new("light".into()),
+            "light".into(),
+            DeviceType::ColorBulb,
+            String::new(),
+        )]);
+
+        repo.send_command(&id, &Command::TurnOn).await.unwrap();
+        repo.send_command(&id, &Command::SetBrightness(BrightnessValue::try_from(40).unwrap()))
+            .await
+            .unwrap();
+
+        let status = repo.get_device_status(&id).await.unwrap();
+        assert_eq!(status.power, Some(PowerState::On));
+        assert_eq!(status.brightness, Some(40));
+        assert_eq!(repo.sent_commands().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn lock_commands_update_lock_state() {
+        let id = DeviceId::new("lock".to_string());
+        let repo = InMemoryDeviceRepository::new(vec![Device::new(
+            DeviceId::new("lock".into()),
+            "lock".into(),
+            DeviceType::SmartLock,
+            String::new(),
+        )]);
+
+        repo.send_command(&id, &Command::Lock).await.unwrap();
+        assert_eq!(repo.status(&id).unwrap().lock_state, Some(LockState::Locked));
+
+        repo.send_command(&id, &Command::Unlock).await.unwrap();
+        assert_eq!(repo.status(&id).unwrap().lock_state, Some(LockState::Unlocked));
+    }
+
+    #[tokio::test]
+    async fn unknown_device_is_not_found() {
+        let repo = InMemoryDeviceRepository::default();
+        let id = DeviceId::new("missing".to_string());
+
+        let err = repo.get_device(&id).await.unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(ApiError::DeviceNotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn scripted_failure_is_returned_once() {
+        let id = DeviceId::new("light".to_string());
+        let repo = InMemoryDeviceRepository::new(vec![Device::new(
+            DeviceId::new("light".into()),
+            "light".into(),
+            DeviceType::ColorBulb,
+            String::new(),
+        )]);
+        repo.fail_next(RepositoryCall::SendCommand, ApiError::DeviceBusy { message: "busy".into() });
+
+        let err = repo.send_command(&id, &Command::TurnOn).await.unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(ApiError::DeviceBusy { .. })));
+        assert_eq!(repo.status(&id).unwrap().power, None);
+
+        repo.send_command(&id, &Command::TurnOn).await.unwrap();
+        assert_eq!(repo.status(&id).unwrap().power, Some(PowerState::On));
+        assert_eq!(repo.sent_commands().len(), 2);
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow, bail};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+const MAX_BODY_SIZE: usize = 1024 * 1024;
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase()).map(|v| v.as_str())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn new(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: body.into(),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            429 => "Too Many Requests",
+            500 => "Internal Server Error",
+            503 => "Service Unavailable",
+            _ => "",
+        }
+    }
+}
+
+pub type HttpHandler = Arc<dyn Fn(HttpRequest) -> HttpResponse + Send + Sync>;
+
+pub async fn serve(listener: TcpListener, handler: HttpHandler) -> Result<()> {
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let handler = handler.clone();
+
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, handler).await {
+                tracing::warn!("{peer}: {e}");
+            }
+        });
+    }
+}
+
+async fn handle_connection(stream: TcpStream, handler: HttpHandler) -> Result<()> {
+    let mut reader = BufReader::new(stream);
+
+    let request = tokio::time::timeout(READ_TIMEOUT, read_request(&mut reader))
+        .await
+        .unwrap_or_else(|_| Err(anyhow!("timed out reading request")));
+
+    let response = match request {
+        Ok(req) => {
+            tracing::debug!("{} {}", req.method, req.path);
+            handler(req)
+        }
+        Err(e) => HttpResponse::new(400, serde_json::json!({ "message": e.to_string() }).to_string()),
+    };
+
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.reason(),
+        response.body.len(),
+    );
+
+    let stream = reader.get_mut();
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(response.body.as_bytes()).await?;
+    stream.shutdown().await?;
+
+    Ok(())
+}
+
+async fn read_request(reader: &mut BufReader<TcpStream>) -> Result<HttpRequest> {
+    let mut line = String::new();
+    reader.read_line(&mut line).await?;
+
+    let mut parts = line.split_whitespace();
+    let method = parts.next().ok_or_else(|| anyhow!("missing method"))?.to_string();
+    let path = parts.next().ok_or_else(|| anyhow!("missing path"))?.to_string();
+
+    let mut headers = HashMap::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            bail!("unexpected end of headers");
+        }
+
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let length = headers
+        .get("content-length")
+        .map(|v| v.parse::<usize>())
+        .transpose()?
+        .unwrap_or(0);
+
+    if length > MAX_BODY_SIZE {
+        bail!("request body too large");
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+
+    Ok(HttpRequest {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
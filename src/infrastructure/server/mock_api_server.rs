@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use base64::Engine as _;
+use base64::engine::general_purpose;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use sha2::Sha256;
+use tokio::net::TcpListener;
+
+use crate::infrastructure::server::http::{self, HttpRequest, HttpResponse};
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MockFixtures {
+    #[serde(default)]
+    pub device_list: Vec<Value>,
+    #[serde(default)]
+    pub infrared_remote_list: Vec<Value>,
+    #[serde(default)]
+    pub statuses: HashMap<String, Value>,
+    #[serde(default)]
+    pub scenes: Vec<Value>,
+}
+
+impl MockFixtures {
+    pub fn load(path: &str) -> Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    fn has_device(&self, id: &str) -> bool {
+        self.device_list
+            .iter()
+            .chain(&self.infrared_remote_list)
+            .any(|v| v["deviceId"] == id)
+    }
+
+    fn has_scene(&self, id: &str) -> bool {
+        self.scenes.iter().any(|v| v["sceneId"] == id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockFailure {
+    Http(u16),
+    StatusCode(i32),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockRequest {
+    pub method: String,
+    pub path: String,
+    pub body: Option<Value>,
+}
+
+#[derive(Debug)]
+struct MockState {
+    token: String,
+    secret: String,
+    fixtures: MockFixtures,
+    failures: Vec<(String, MockFailure, u32)>,
+    requests: Vec<MockRequest>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MockApiHandle {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockApiHandle {
+    pub fn set_status(&self, device_id: &str, status: Value) {
+        self.state.lock().unwrap().fixtures.statuses.insert(device_id.to_string(), status);
+    }
+
+    pub fn fail(&self, path_prefix: &str, failure: MockFailure, times: u32) {
+        self.state
+            .lock()
+            .unwrap()
+            .failures
+            .push((path_prefix.to_string(), failure, times));
+    }
+
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    pub fn commands(&self, device_id: &str) -> Vec<Value> {
+        let path = format!("/devices/{device_id}/commands");
+
+        self.requests()
+            .into_iter()
+            .filter(|v| v.method == "POST" && v.path == path)
+            .filter_map(|v| v.body)
+            .collect()
+    }
+}
+
+pub struct MockApiServer {
+    listener: TcpListener,
+    handle: MockApiHandle,
+}
+
+impl MockApiServer {
+    pub async fn bind(addr: &str, token: &str, secret: &str, fixtures: MockFixtures) -> Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        let state = MockState {
+            token: token.to_string(),
+            secret: secret.to_string(),
+            fixtures,
+            failures: Vec::new(),
+            requests: Vec::new(),
+        };
+
+        Ok(Self {
+            listener,
+            handle: MockApiHandle {
+                state: Arc::new(Mutex::new(state)),
+            },
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    pub fn handle(&self) -> MockApiHandle {
+        self.handle.clone()
+    }
+
+    pub async fn run(self) -> Result<()> {
+        let state = self.handle.state;
+        let handler = Arc::new(move |req: HttpRequest| {
+            let mut state = state.lock().unwrap();
+            handle_request(&mut state, req)
+        });
+
+        http::serve(self.listener, handler).await
+    }
+}
+
+fn success(body: Value) -> HttpResponse {
+    HttpResponse::new(
+        200,
+        json!({ "statusCode": 100, "message": "success", "body": body }).to_string(),
+    )
+}
+
+fn failure(status_code: i32, message: &str) -> HttpResponse {
+    HttpResponse::new(
+        200,
+        json!({ "statusCode": status_code, "message": message, "body": {} }).to_string(),
+    )
+}
+
+fn verify_signature(state: &MockState, req: &HttpRequest) -> bool {
+    let (Some(token), Some(t), Some(nonce), Some(sign)) = (
+        req.header("authorization"),
+        req.header("t"),
+        req.header("nonce"),
+        req.header("sign"),
+    ) else {
+        return false;
+    };
+
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(state.secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(format!("{token}{t}{nonce}").as_bytes());
+    let expected = general_purpose::STANDARD.encode(mac.finalize().into_bytes());
+
+    token == state.token && sign == expected
+}
+
+fn take_failure(state: &mut MockState, path: &str) -> Option<MockFailure> {
+    let index = state
+        .failures
+        .iter()
+        .position(|(prefix, _, times)| *times > 0 && path.starts_with(prefix.as_str()))?;
+
+    let (_, failure, times) = &mut state.failures[index];
+    *times -= 1;
+    Some(*failure)
+}
+
+fn handle_request(state: &mut MockState, req: HttpRequest) -> HttpResponse {
+    let body = serde_json::from_slice::<Value>(&req.body).ok();
+    state.requests.push(MockRequest {
+        method: req.method.clone(),
+        path: req.path.clone(),
+        body: body.clone(),
+    });
+
+    if !verify_signature(state, &req) {
+        return HttpResponse::new(401, r#"{"message":"Unauthorized"}"#);
+    }
+
+    match take_failure(state, &req.path) {
+        Some(MockFailure::Http(status)) => {
+            return HttpResponse::new(status, r#"{"message":"injected failure"}"#);
+        }
+        Some(MockFailure::StatusCode(status_code)) => {
+            return failure(status_code, "injected failure");
+        }
+        None => {}
+    }
+
+    let segments: Vec<&str> = req.path.trim_matches('/').split('/').collect();
+    let fixtures = &state.fixtures;
+
+    match (req.method.as_str(), segments.as_slice()) {
+        ("GET", ["devices"]) => success(json!({
+            "deviceList": fixtures.device_list,
+            "infraredRemoteList": fixtures.infrared_remote_list,
+        })),
+        ("GET", ["devices", id, "status"]) => match fixtures.statuses.get(*id) {
+            Some(status) => success(status.clone()),
+            None => failure(152, "device not found"),
+        },
+        ("POST", ["devices", id, "commands"]) => match (fixtures.has_device(id), body) {
+            (false, _) => failure(152, "device not found"),
+            (true, Some(body)) if body["command"].is_string() => success(json!({})),
+            (true, _) => HttpResponse::new(400, r#"{"message":"invalid command body"}"#),
+        },
+        ("GET", ["scenes"]) => success(json!(fixtures.scenes)),
+        ("POST", ["scenes", id, "execute"]) => match fixtures.has_scene(id) {
+            true => success(json!({})),
+            false => failure(152, "scene not found"),
+        },
+        _ => HttpResponse::new(404, r#"{"message":"not found"}"#),
+    }
+}
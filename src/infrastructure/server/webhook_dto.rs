@@ -0,0 +1,215 @@
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::domain::models::events::DeviceEvent;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct WebhookPayloadDto {
+    pub(super) event_type: String,
+    pub(super) context: Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContextHeaderDto {
+    device_type: String,
+    device_mac: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MotionContextDto {
+    detection_state: String,
+    time_of_sample: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContactContextDto {
+    detection_state: String,
+    open_state: String,
+    time_of_sample: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LockContextDto {
+    lock_state: String,
+    time_of_sample: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MeterContextDto {
+    temperature: f32,
+    humidity: u8,
+    scale: String,
+    time_of_sample: i64,
+}
+
+impl TryFrom<WebhookPayloadDto> for DeviceEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(dto: WebhookPayloadDto) -> Result<Self> {
+        let ContextHeaderDto { device_type, device_mac } = serde_json::from_value(dto.context.clone())?;
+
+        let event = match device_type.as_str() {
+            "WoPresence" => {
+                let v: MotionContextDto = serde_json::from_value(dto.context)?;
+                DeviceEvent::Motion {
+                    device_mac,
+                    detected: v.detection_state == "DETECTED",
+                    time_of_sample: v.time_of_sample,
+                }
+            }
+            "WoContact" => {
+                let v: ContactContextDto = serde_json::from_value(dto.context)?;
+                DeviceEvent::Contact {
+                    device_mac,
+                    detected: v.detection_state == "DETECTED",
+                    open_state: v.open_state,
+                    time_of_sample: v.time_of_sample,
+                }
+            }
+            "WoLock" | "WoLockPro" => {
+                let v: LockContextDto = serde_json::from_value(dto.context)?;
+                DeviceEvent::Lock {
+                    device_mac,
+                    lock_state: v.lock_state,
+                    time_of_sample: v.time_of_sample,
+                }
+            }
+            "WoMeter" | "WoMeterPlus" | "WoIOSensor" | "WoMeterPro" | "WoMeterProCO2" => {
+                let v: MeterContextDto = serde_json::from_value(dto.context)?;
+                DeviceEvent::Meter {
+                    device_mac,
+                    temperature: v.temperature,
+                    humidity: v.humidity,
+                    scale: v.scale,
+                    time_of_sample: v.time_of_sample,
+                }
+            }
+            _ => DeviceEvent::Other {
+                device_type,
+                device_mac,
+                context: dto.context,
+            },
+        };
+
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn parse(payload: Value) -> Result<DeviceEvent> {
+        DeviceEvent::try_from(serde_json::from_value::<WebhookPayloadDto>(payload)?)
+    }
+
+    fn payload(context: Value) -> Value {
+        json!({ "eventType": "changeReport", "eventVersion": "1", "context": context })
+    }
+
+    #[test]
+    fn parses_motion_sensor_event() {
+        let event = parse(payload(json!({
+            "deviceType": "WoPresence",
+            "deviceMac": "01:00:5e:90:10:00",
+            "detectionState": "DETECTED",
+            "timeOfSample": 123456789,
+        })))
+        .unwrap();
+
+        assert!(matches!(
+            event,
+            DeviceEvent::Motion { ref device_mac, detected: true, time_of_sample: 123456789 }
+                if device_mac == "01:00:5e:90:10:00"
+        ));
+    }
+
+    #[test]
+    fn parses_contact_sensor_event() {
+        let event = parse(payload(json!({
+            "deviceType": "WoContact",
+            "deviceMac": "01:00:5e:90:10:00",
+            "detectionState": "NOT_DETECTED",
+            "doorMode": "OUT_DOOR",
+            "brightness": "dim",
+            "openState": "open",
+            "timeOfSample": 123456789,
+        })))
+        .unwrap();
+
+        assert!(matches!(
+            event,
+            DeviceEvent::Contact { detected: false, ref open_state, .. } if open_state == "open"
+        ));
+    }
+
+    #[test]
+    fn parses_lock_event() {
+        let event = parse(payload(json!({
+            "deviceType": "WoLock",
+            "deviceMac": "01:00:5e:90:10:00",
+            "lockState": "LOCKED",
+            "timeOfSample": 123456789,
+        })))
+        .unwrap();
+
+        assert!(matches!(event, DeviceEvent::Lock { ref lock_state, .. } if lock_state == "LOCKED"));
+    }
+
+    #[test]
+    fn parses_meter_event() {
+        let event = parse(payload(json!({
+            "deviceType": "WoMeter",
+            "deviceMac": "01:00:5e:90:10:00",
+            "temperature": 22.5,
+            "scale": "CELSIUS",
+            "humidity": 31,
+            "timeOfSample": 123456789,
+        })))
+        .unwrap();
+
+        assert!(matches!(
+            event,
+            DeviceEvent::Meter { temperature, humidity: 31, ref scale, .. }
+                if temperature == 22.5 && scale == "CELSIUS"
+        ));
+    }
+
+    #[test]
+    fn unknown_device_type_keeps_raw_context() {
+        let context = json!({
+            "deviceType": "WoFuture",
+            "deviceMac": "01:00:5e:90:10:00",
+            "power": "on",
+        });
+
+        let event = parse(payload(context.clone())).unwrap();
+
+        assert!(matches!(
+            event,
+            DeviceEvent::Other { ref device_type, context: ref v, .. }
+                if device_type == "WoFuture" && *v == context
+        ));
+    }
+
+    #[test]
+    fn malformed_context_is_rejected() {
+        let result = parse(payload(json!({
+            "deviceType": "WoMeter",
+            "deviceMac": "01:00:5e:90:10:00",
+            "humidity": "wet",
+        })));
+
+        assert!(result.is_err());
+        assert!(parse(json!({ "eventType": "changeReport" })).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::Result;
+use tokio::net::TcpListener;
+
+use crate::domain::models::events::DeviceEvent;
+use crate::infrastructure::server::http::{self, HttpResponse};
+use crate::infrastructure::server::webhook_dto::WebhookPayloadDto;
+
+pub struct WebhookServer {
+    listener: TcpListener,
+}
+
+impl WebhookServer {
+    pub async fn bind(addr: &str) -> Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        Ok(Self { listener })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    pub async fn run<F>(self, on_event: F) -> Result<()>
+    where
+        F: Fn(DeviceEvent) -> Result<()> + Send + Sync + 'static,
+    {
+        let handler = Arc::new(move |req: http::HttpRequest| {
+            if req.method != "POST" {
+                return HttpResponse::new(405, r#"{"message":"method not allowed"}"#);
+            }
+
+            let event = serde_json::from_slice::<WebhookPayloadDto>(&req.body)
+                .map_err(anyhow::Error::from)
+                .and_then(|dto| {
+                    tracing::debug!("{:?}", dto.event_type);
+                    DeviceEvent::try_from(dto)
+                });
+
+            match event.and_then(&on_event) {
+                Ok(()) => HttpResponse::new(200, r#"{"message":"success"}"#),
+                Err(e) => {
+                    tracing::warn!("{e}");
+                    HttpResponse::new(400, serde_json::json!({ "message": e.to_string() }).to_string())
+                }
+            }
+        });
+
+        http::serve(self.listener, handler).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+
+    use super::*;
+
+    async fn post(addr: SocketAddr, body: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let request = format!(
+            "POST / HTTP/1.1\r\nHost: {addr}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn sample_payload_is_accepted_and_malformed_body_is_rejected() {
+        let server = WebhookServer::bind("127.0.0.1:0").await.unwrap();
+        let addr = server.local_addr().unwrap();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let received = events.clone();
+        tokio::spawn(server.run(move |event| {
+            received.lock().unwrap().push(event);
+            Ok(())
+        }));
+
+        let response = post(
+            addr,
+            r#"{"eventType":"changeReport","eventVersion":"1","context":{"deviceType":"WoPresence","deviceMac":"01:00:5e:90:10:00","detectionState":"DETECTED","timeOfSample":123456789}}"#,
+        )
+        .await;
+        assert!(response.starts_with("HTTP/1.1 200"));
+
+        let response = post(addr, r#"{"eventType": "#).await;
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        assert!(head.starts_with("HTTP/1.1 400"));
+        assert!(serde_json::from_str::<serde_json::Value>(body).unwrap()["message"].is_string());
+
+        assert_eq!(events.lock().unwrap().len(), 1);
+    }
+}
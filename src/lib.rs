@@ -3,21 +3,75 @@
 #![allow(unused_imports)]
 
 pub mod domain {
+    pub mod errors {
+        mod api_error;
+        pub use api_error::{ApiError, STATUS_SUCCESS};
+
+        mod validation_error;
+        pub use validation_error::ValidationError;
+    }
+
     pub mod repositories {
         mod device_repository_interface;
         pub use device_repository_interface::IDeviceRepository;
+
+        mod scene_repository_interface;
+        pub use scene_repository_interface::ISceneRepository;
+
+        mod webhook_repository_interface;
+        pub use webhook_repository_interface::IWebhookRepository;
+
+        mod quota_repository_interface;
+        pub use quota_repository_interface::IQuotaRepository;
     }
 
     pub mod models {
         pub mod entities {
             mod device;
             pub use device::Device;
+
+            mod device_status;
+            pub use device_status::DeviceStatus;
+
+            mod scene;
+            pub use scene::Scene;
+
+            mod webhook;
+            pub use webhook::Webhook;
+
+            mod api_quota;
+            pub use api_quota::ApiQuota;
+        }
+
+        pub mod capabilities {
+            mod device_capabilities;
+            pub use device_capabilities::{supported_commands, validate_bot_mode, validate_command};
+        }
+
+        pub mod events {
+            mod device_event;
+            pub use device_event::DeviceEvent;
         }
         
         pub mod value_objects {
             mod device_id;
             pub use device_id::DeviceId;
 
+            mod device_type;
+            pub use device_type::DeviceType;
+
+            mod power_state;
+            pub use power_state::PowerState;
+
+            mod lock_state;
+            pub use lock_state::{DoorState, LockState};
+
+            mod bot_mode;
+            pub use bot_mode::BotMode;
+
+            mod scene_id;
+            pub use scene_id::SceneId;
+
             mod command;
             pub use command::*;
         }
@@ -28,14 +82,62 @@ pub mod infrastructure {
     pub mod api {
         mod switchbot_api;
         pub use switchbot_api::SwitchBotApi;
+
+        mod retry_policy;
+        pub use retry_policy::{RetryOn, RetryPolicy};
+
+        mod call_budget;
+        pub use call_budget::CallBudget;
+
+        mod http_settings;
+        pub use http_settings::HttpSettings;
+
+        mod clock;
+        pub use clock::{IClock, SystemClock};
+
+        mod nonce_generator;
+        pub use nonce_generator::{INonceGenerator, RandomNonceGenerator};
+
+        mod cassette;
+        pub use cassette::{Cassette, CassetteMode, Interaction, RecordedRequest, RecordedResponse};
     }
 
     pub mod io {
         mod device_file_writer;
         pub use device_file_writer::DeviceFileWriter;
 
+        mod device_file_reader;
+        pub use device_file_reader::DeviceFileReader;
+
         mod json_alias_loader;
         pub use json_alias_loader::JsonAliasLoader;
+
+        mod event_line_writer;
+        pub use event_line_writer::EventLineWriter;
+
+        mod quota_file_store;
+        pub use quota_file_store::{QuotaFileStore, QuotaRecord};
+    }
+
+    pub mod memory {
+        mod in_memory_device_repository;
+        pub use in_memory_device_repository::{InMemoryDeviceRepository, RepositoryCall};
+    }
+
+    pub mod server {
+        pub mod http;
+
+        mod webhook_dto;
+
+        mod webhook_server;
+        pub use webhook_server::WebhookServer;
+
+        #[cfg(feature = "mock-server")]
+        mod mock_api_server;
+        #[cfg(feature = "mock-server")]
+        pub use mock_api_server::{
+            MockApiHandle, MockApiServer, MockFailure, MockFixtures, MockRequest,
+        };
     }
 }
 
@@ -44,13 +146,37 @@ pub mod application {
         mod control_device;
         pub use control_device::{IControlDeviceUseCase, ControlDeviceUseCase};
 
+        mod run_scene;
+        pub use run_scene::{IRunSceneUseCase, RunSceneUseCase};
+
+        mod manage_webhook;
+        pub use manage_webhook::{IManageWebhookUseCase, ManageWebhookUseCase};
+
+        mod listen_events;
+        pub use listen_events::{IListenEventsUseCase, ListenEventsUseCase};
+
+        mod check_quota;
+        pub use check_quota::{ICheckQuotaUseCase, CheckQuotaUseCase};
+
         pub mod export_devices;
         pub use export_devices::export_devices_to_file;
+
+        pub mod import_devices;
+        pub use import_devices::import_devices_from_file;
     }
 
     pub mod dto {
         mod device_dto;
         pub use device_dto::*;
+
+        mod scene_dto;
+        pub use scene_dto::*;
+
+        mod webhook_dto;
+        pub use webhook_dto::*;
+
+        mod quota_dto;
+        pub use quota_dto::*;
     }
 
     pub mod adapter {
@@ -66,8 +192,17 @@ pub mod presentation {
         mod args;
         pub use args::Args;
         pub use args::Commands;
+        pub use args::SceneCommands;
+        pub use args::VacuumCommands;
+        pub use args::WebhookCommands;
 
         mod dispatcher;
         pub use dispatcher::Dispatcher;
+
+        mod exit_code;
+        pub use exit_code::ExitCode;
+
+        mod output_format;
+        pub use output_format::OutputFormat;
     }
 }
@@ -1,45 +1,114 @@
+use std::time::Duration;
+
 use anyhow::Result;
-use dotenvy;
-use tracing;
+use clap::Parser;
 use tracing_subscriber::{self, EnvFilter};
 use tracing_subscriber::fmt::time::LocalTime;
 
 use switchbot_cli_tool::application::adapter::alias::AliasResolver;
-use switchbot_cli_tool::application::services::ControlDeviceUseCase;
-use switchbot_cli_tool::infrastructure::api::SwitchBotApi;
+use switchbot_cli_tool::application::services::{
+    CheckQuotaUseCase, ControlDeviceUseCase, ListenEventsUseCase, ManageWebhookUseCase,
+    RunSceneUseCase,
+};
+use switchbot_cli_tool::infrastructure::api::{
+    CallBudget, Cassette, HttpSettings, RetryOn, RetryPolicy, SwitchBotApi,
+};
 use switchbot_cli_tool::infrastructure::io::JsonAliasLoader;
 use switchbot_cli_tool::presentation::cli;
 
 mod config;
 use config::Config;
 
-fn tracing_init() -> Result<()> {
-    dotenvy::dotenv()?;
+fn tracing_init() {
+    dotenvy::dotenv().ok();
     tracing_subscriber::fmt()
         .with_timer(LocalTime::rfc_3339())
         .with_env_filter(EnvFilter::from_default_env())
+        .with_writer(std::io::stderr)
         .init();
-
-    Ok(())
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    _ = tracing_init()?;
+fn report_error(e: &anyhow::Error) -> i32 {
+    if let Some(e) = e.downcast_ref::<clap::Error>() {
+        let _ = e.print();
+        return e.exit_code();
+    }
 
+    eprintln!("error: {e:#}");
+    cli::ExitCode::from_error(e).code()
+}
+
+async fn run() -> Result<()> {
+    let args = cli::Args::try_parse()?;
     let config = Config::from_env()?;
 
-    let api = SwitchBotApi::new(config.host, config.token, config.secret);
+    let mut http_settings = HttpSettings {
+        connect_timeout: Duration::from_millis(config.connect_timeout_ms),
+        timeout: Duration::from_millis(config.timeout_ms),
+        proxy: config.proxy,
+        no_proxy: config.no_proxy,
+        ca_bundle: config.ca_bundle,
+        ..HttpSettings::default()
+    };
+    if let Some(user_agent) = config.user_agent {
+        http_settings.user_agent = user_agent;
+    }
+
+    let mut retry_policy = RetryPolicy {
+        max_attempts: config.retry_max_attempts,
+        base_delay: Duration::from_millis(config.retry_base_delay_ms),
+        max_delay: Duration::from_millis(config.retry_max_delay_ms),
+        ..RetryPolicy::default()
+    };
+    if let Some(retry_on) = config.retry_on {
+        retry_policy.retry_on = RetryOn::parse_list(&retry_on)?;
+    }
+
+    let call_budget = CallBudget {
+        path: config.quota_file,
+        daily_limit: config.quota_daily_limit,
+        reserve: config.quota_reserve,
+        warn_at: config.quota_warn_at,
+    };
+
+    let mut api = SwitchBotApi::new(config.host, config.token, config.secret, &http_settings)?
+        .with_retry_policy(retry_policy)
+        .with_call_budget(call_budget)
+        .with_dry_run(args.dry_run);
+    if let Some(dir) = &args.record {
+        api = api.with_cassette(Cassette::recorder(dir)?);
+    }
+    if let Some(dir) = &args.replay {
+        api = api.with_cassette(Cassette::player(dir)?);
+    }
     let use_case = ControlDeviceUseCase::new(&api);
+    let scene_use_case = RunSceneUseCase::new(&api);
+    let webhook_use_case = ManageWebhookUseCase::new(&api);
+    let listen_use_case = ListenEventsUseCase::new();
+    let quota_use_case = CheckQuotaUseCase::new(&api);
     let resolver = AliasResolver::new(JsonAliasLoader::load("device_aliases.json")?.0);
     let dispatcher = cli::Dispatcher::new(
         &use_case,
+        &scene_use_case,
+        &webhook_use_case,
+        &listen_use_case,
+        &quota_use_case,
         &resolver
     );
 
+    dispatcher.dispatch(args).await
+}
+
+#[tokio::main]
+async fn main() {
+    tracing_init();
+
     tracing::info!("Process Start");
-    _ = dispatcher.dispatch().await;
+    let result = run().await;
     tracing::info!("Process End");
 
-    Ok(())
+    if let Err(e) = result {
+        tracing::debug!("{e:?}");
+        std::process::exit(report_error(&e));
+    }
 }
@@ -1,16 +1,34 @@
 use clap::{Parser, Subcommand};
 
+use crate::presentation::cli::OutputFormat;
+
 #[derive(Parser, Debug)]
 #[command(name = "switchbot-cli")]
 #[command(about = "CLI for controlling SwitchBot devices", long_about = None)]
 pub struct Args {
     #[command(subcommand)]
     pub command: Commands,
+
+    #[arg(long, global = true, value_name = "DIR", conflicts_with = "replay")]
+    pub record: Option<String>,
+
+    #[arg(long, global = true, value_name = "DIR")]
+    pub replay: Option<String>,
+
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+
+    #[arg(short, long, global = true, value_enum, default_value = "table")]
+    pub output: OutputFormat,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     List,
+    Status {
+        #[arg(short, long)]
+        device: String,
+    },
     Exec {
         #[arg(short, long)]
         device: String,
@@ -23,5 +41,97 @@ pub enum Commands {
 
         #[arg(short='C', long)]
         customize: bool,
+
+        #[arg(short='j', long, conflicts_with = "values")]
+        param_json: Option<String>,
+
+        #[arg(short, long)]
+        wait: bool,
+
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        repeat: u32,
+
+        #[arg(short, long, default_value_t = 500)]
+        interval_ms: u64,
+    },
+    Vacuum {
+        #[arg(short, long)]
+        device: String,
+
+        #[command(subcommand)]
+        command: VacuumCommands,
+    },
+    Scenes {
+        #[command(subcommand)]
+        command: SceneCommands,
+    },
+    Webhook {
+        #[command(subcommand)]
+        command: WebhookCommands,
+    },
+    Quota,
+    Listen {
+        #[arg(short, long, default_value = "127.0.0.1:8080")]
+        addr: String,
+
+        #[arg(short, long, alias = "out-file")]
+        file: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SceneCommands {
+    List,
+    Run {
+        scene: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum WebhookCommands {
+    Setup {
+        #[arg(short, long)]
+        url: String,
+    },
+    Query {
+        #[arg(short, long, num_args=1..)]
+        url: Option<Vec<String>>,
+    },
+    Update {
+        #[arg(short, long)]
+        url: String,
+
+        #[arg(long, conflicts_with = "disable", required_unless_present = "disable")]
+        enable: bool,
+
+        #[arg(long)]
+        disable: bool,
+    },
+    Delete {
+        #[arg(short, long)]
+        url: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum VacuumCommands {
+    Start,
+    Stop,
+    Dock,
+    PowerLevel {
+        level: u8,
+    },
+    Clean {
+        #[arg(short, long, default_value = "sweep")]
+        action: String,
+
+        #[arg(short, long, default_value_t = 2)]
+        fan_level: u8,
+
+        #[arg(short, long, default_value_t = 1)]
+        water_level: u8,
+
+        #[arg(short, long, default_value_t = 1)]
+        times: u32,
     },
 }
@@ -1,111 +1,389 @@
+use std::time::Duration;
+
 use anyhow::{Result, anyhow};
-use clap::Parser;
 use tracing::{self, instrument};
 
 use crate::application::adapter::alias::AliasResolver;
-use crate::application::services::{ControlDeviceUseCase, IControlDeviceUseCase};
-use crate::application::dto::ExecuteCommandDto;
+use crate::application::services::{
+    ControlDeviceUseCase, ICheckQuotaUseCase, IControlDeviceUseCase, IListenEventsUseCase,
+    IManageWebhookUseCase, IRunSceneUseCase,
+};
+use crate::application::dto::{ExecuteCommandDto, ExecuteSceneDto};
 use crate::domain::models::value_objects::{
     BrightnessValue, ColorTemperatureValue, ColorValues, Command
 };
 use crate::domain::models::value_objects::{
     AcPowerState, AcValues, AcMode, AcFanSpeed,
 };
+use crate::domain::models::value_objects::{
+    BlindTiltDirection, BlindTiltPositionValues, CurtainMode, CurtainPositionValues,
+    PositionValue, TiltAngleValue,
+};
+use crate::domain::models::value_objects::{
+    VacuumCleanAction, VacuumCleanTimesValue, VacuumCleanValues, VacuumFanLevelValue,
+    VacuumPowerLevelValue, VacuumWaterLevelValue,
+};
+use crate::domain::models::value_objects::{
+    EvaporativeHumidifierMode, EvaporativeHumidifierValues, HumidifierMode, HumidityValue,
+};
+use crate::domain::models::value_objects::{ChannelValue, IrFanAction, IrLightAction};
+use crate::domain::models::value_objects::parse_parameter;
+use crate::domain::errors::ValidationError;
 use crate::domain::repositories::IDeviceRepository;
-use crate::presentation::cli::{Args, Commands};
+use crate::presentation::cli::{Args, Commands, SceneCommands, VacuumCommands, WebhookCommands};
 pub struct Dispatcher<'a> {
     use_case: &'a dyn IControlDeviceUseCase,
+    scene_use_case: &'a dyn IRunSceneUseCase,
+    webhook_use_case: &'a dyn IManageWebhookUseCase,
+    listen_use_case: &'a dyn IListenEventsUseCase,
+    quota_use_case: &'a dyn ICheckQuotaUseCase,
     resolver: &'a AliasResolver,
 }
 
 impl<'a> Dispatcher<'a> {
     pub fn new(
         use_case: &'a dyn IControlDeviceUseCase, 
+        scene_use_case: &'a dyn IRunSceneUseCase,
+        webhook_use_case: &'a dyn IManageWebhookUseCase,
+        listen_use_case: &'a dyn IListenEventsUseCase,
+        quota_use_case: &'a dyn ICheckQuotaUseCase,
         resolver: &'a AliasResolver 
     ) -> Self {
         Self {
             use_case,
+            scene_use_case,
+            webhook_use_case,
+            listen_use_case,
+            quota_use_case,
             resolver
         }
     }
 
-    pub async fn dispatch(&self) -> Result<()> {
-        let args: Args = Args::try_parse().map_err(|e| {
-            tracing::error!("{e}");
-            std::process::exit(e.use_stderr() as i32);
-        })?;
-    
+    pub async fn dispatch(&self, args: Args) -> Result<()> {
+        if args.dry_run && is_read_only(&args.command) {
+            println!("Dry run: read-only commands are not sent");
+            return Ok(());
+        }
+
         match args.command {
             Commands::List => {
-                let devices = self.use_case.fetch_devices().await?;
-                devices.into_iter().for_each(|v| println!("{v:?}"));
+                let devices: Vec<_> = self
+                    .use_case
+                    .fetch_devices()
+                    .await?
+                    .into_iter()
+                    .map(|v| {
+                        let aliases = self.resolver.aliases_for(&v.device_id);
+                        v.with_aliases(aliases)
+                    })
+                    .collect();
+                print!("{}", args.output.render(&devices)?);
+            }
+            Commands::Status { device } => {
+                let device_id = self.resolver.resolve(device.as_str()).to_string();
+                let status = self.use_case.fetch_status(device_id).await?;
+                print!("{}", args.output.render_one(&status)?);
             }
             Commands::Exec {
                 device,
                 command,
                 values,
                 customize,
+                param_json,
+                wait,
+                repeat,
+                interval_ms,
             } => {
                 tracing::debug!("{device:?} {command:?} {values:?}");
 
                 let device_id = self.resolver.resolve(device.as_str()).to_string();
-                let command = match command.as_str() {
-                    "on" => Command::TurnOn,
-                    "off" => Command::TurnOff,
-                    "brightness" => Command::SetBrightness(BrightnessValue::try_from(
-                        values
-                            .as_ref()
-                            .and_then(|v| v.get(0))
-                            .ok_or_else(|| anyhow!("value does not exist"))?
-                            .parse::<u8>()?,
-                    )?),
-                    "color" => {
-                        let [r, g, b]: [u8; 3] = values
-                            .ok_or_else(|| anyhow!("No values"))?
-                            .iter()
-                            .map(|s| s.parse::<u8>().map_err(|e| anyhow!(e)))
-                            .collect::<Result<Vec<u8>>>()?
-                            .as_slice()
-                            .try_into()?;
-
-                        Command::SetColor(ColorValues::try_from((r, g, b))?)
-                    }
-                    "color_temp" => Command::SetColorTemperature(ColorTemperatureValue::try_from(
-                        values
-                            .as_ref()
-                            .and_then(|v| v.get(0))
-                            .ok_or_else(|| anyhow!("value does not exist"))?
-                            .parse::<u16>()?,
-                    )?),
-                    "ac" => {
-                        let values:[String; 4] = values
-                            .ok_or_else(|| anyhow!("value does not exist"))?
-                            .try_into()
-                            .map_err(|_| anyhow!("invalid number of elements."))?;
-
-                        let temperature = values[0].parse::<u8>()?;
-                        let mode = values[1].to_lowercase().parse::<AcMode>()?;
-                        let fan_speed = values[2].to_lowercase().parse::<AcFanSpeed>()?;
-                        let power_state = values[3].to_lowercase().parse::<AcPowerState>()?;
-
-                        let values = AcValues{temperature, mode, fan_speed, power_state};
-                        Command::AcSetAll(values)
-                    }
-                    other => Command::Custom {
-                        command_type: if customize {"customize".into()} else {"command".into()},
-                        command,
-                        parameter: "default".into(),
-                    },
-                };
-
-                let _ = self.use_case
-                    .execute(ExecuteCommandDto::new(device_id, command))
+                let command = parse_exec_command(command, values, customize, param_json)
+                    .map_err(ValidationError::from)?;
+
+                self.use_case
+                    .execute(
+                        ExecuteCommandDto::new(device_id, command)
+                            .with_wait(wait && !args.dry_run)
+                            .with_repeat(repeat, Duration::from_millis(interval_ms))
+                            .with_offline(args.dry_run),
+                    )
+                    .await?;
+            }
+            Commands::Vacuum { device, command } => {
+                let device_id = self.resolver.resolve(device.as_str()).to_string();
+                let command = parse_vacuum_command(command).map_err(ValidationError::from)?;
+
+                self.use_case
+                    .execute(ExecuteCommandDto::new(device_id, command).with_offline(args.dry_run))
                     .await?;
             }
+            Commands::Scenes { command } => match command {
+                SceneCommands::List => {
+                    let scenes = self.scene_use_case.fetch_scenes().await?;
+                    print!("{}", args.output.render(&scenes)?);
+                }
+                SceneCommands::Run { scene } => {
+                    let scene = self.resolver.resolve(scene.as_str()).to_string();
+                    self.scene_use_case
+                        .execute(ExecuteSceneDto::new(scene).with_offline(args.dry_run))
+                        .await?;
+                }
+            },
+            Commands::Webhook { command } => match command {
+                WebhookCommands::Setup { url } => {
+                    self.webhook_use_case.setup(url).await?;
+                }
+                WebhookCommands::Query { url } => {
+                    let webhooks = self.webhook_use_case.query(url.unwrap_or_default()).await?;
+                    print!("{}", args.output.render(&webhooks)?);
+                }
+                WebhookCommands::Update { url, enable, disable } => {
+                    self.webhook_use_case.update(url, enable && !disable).await?;
+                }
+                WebhookCommands::Delete { url } => {
+                    self.webhook_use_case.delete(url).await?;
+                }
+            },
+            Commands::Quota => {
+                let quota = self.quota_use_case.fetch_quota().await?;
+                print!("{}", args.output.render_one(&quota)?);
+            }
+            Commands::Listen { addr, file } => {
+                self.listen_use_case.listen(addr, file).await?;
+            }
         }
 
         Ok(())
     }
 }
 
+fn is_read_only(command: &Commands) -> bool {
+    matches!(
+        command,
+        Commands::List
+            | Commands::Status { .. }
+            | Commands::Scenes { command: SceneCommands::List }
+            | Commands::Webhook { command: WebhookCommands::Query { .. } }
+    )
+}
+
+fn parse_exec_command(
+    command: String,
+    values: Option<Vec<String>>,
+    customize: bool,
+    param_json: Option<String>,
+) -> Result<Command> {
+    if customize {
+        return parse_custom_command(command, values, customize, param_json);
+    }
+
+    let has_param_json = param_json.is_some();
+    let command = match command.as_str() {
+        "on" => Command::TurnOn,
+        "off" => Command::TurnOff,
+        "brightness" => Command::SetBrightness(BrightnessValue::try_from(
+            values
+                .as_ref()
+                .and_then(|v| v.first())
+                .ok_or_else(|| anyhow!("value does not exist"))?
+                .parse::<u8>()?,
+        )?),
+        "color" => {
+            let [r, g, b]: [u8; 3] = values
+                .ok_or_else(|| anyhow!("No values"))?
+                .iter()
+                .map(|s| s.parse::<u8>().map_err(|e| anyhow!(e)))
+                .collect::<Result<Vec<u8>>>()?
+                .as_slice()
+                .try_into()?;
+
+            Command::SetColor(ColorValues::try_from((r, g, b))?)
+        }
+        "color_temp" => Command::SetColorTemperature(ColorTemperatureValue::try_from(
+            values
+                .as_ref()
+                .and_then(|v| v.first())
+                .ok_or_else(|| anyhow!("value does not exist"))?
+                .parse::<u16>()?,
+        )?),
+        "ac" => {
+            let values:[String; 4] = values
+                .ok_or_else(|| anyhow!("value does not exist"))?
+                .try_into()
+                .map_err(|_| anyhow!("invalid number of elements."))?;
+
+            let temperature = values[0].parse::<u8>()?;
+            let mode = values[1].to_lowercase().parse::<AcMode>()?;
+            let fan_speed = values[2].to_lowercase().parse::<AcFanSpeed>()?;
+            let power_state = values[3].to_lowercase().parse::<AcPowerState>()?;
+
+            let values = AcValues{temperature, mode, fan_speed, power_state};
+            Command::AcSetAll(values)
+        }
+        "curtain" => {
+            let values = values.ok_or_else(|| anyhow!("value does not exist"))?;
+
+            let position = values
+                .first()
+                .ok_or_else(|| anyhow!("value does not exist"))?
+                .parse::<u8>()?;
+            let mode = values
+                .get(1)
+                .map(|v| v.to_lowercase().parse::<CurtainMode>())
+                .transpose()?
+                .unwrap_or(CurtainMode::DEFAULT);
+            let index = values
+                .get(2)
+                .map(|v| v.parse::<u8>())
+                .transpose()?
+                .unwrap_or(0);
+
+            let position = PositionValue::try_from(position)?;
+            Command::CurtainSetPosition(CurtainPositionValues{index, mode, position})
+        }
+        "blind" => {
+            let values:[String; 2] = values
+                .ok_or_else(|| anyhow!("value does not exist"))?
+                .try_into()
+                .map_err(|_| anyhow!("invalid number of elements."))?;
+
+            let direction = values[0].to_lowercase().parse::<BlindTiltDirection>()?;
+            let angle = TiltAngleValue::try_from(values[1].parse::<u8>()?)?;
 
+            Command::BlindTiltSetPosition(BlindTiltPositionValues{direction, angle})
+        }
+        "channel" => Command::SetChannel(ChannelValue::try_from(
+            values
+                .as_ref()
+                .and_then(|v| v.first())
+                .ok_or_else(|| anyhow!("value does not exist"))?
+                .parse::<u16>()?,
+        )?),
+        "volume_up" => Command::VolumeAdd,
+        "volume_down" => Command::VolumeSub,
+        "channel_up" => Command::ChannelAdd,
+        "channel_down" => Command::ChannelSub,
+        "mute" => Command::SetMute,
+        "swing" | "timer" | "low_speed" | "middle_speed" | "high_speed" => {
+            Command::IrFan(command.parse::<IrFanAction>()?)
+        }
+        "brightness_up" | "brightness_down" => {
+            Command::IrLight(command.parse::<IrLightAction>()?)
+        }
+        "lock" => Command::Lock,
+        "unlock" => Command::Unlock,
+        "deadbolt" => Command::Deadbolt,
+        "press" => Command::Press,
+        "humidifier" => Command::HumidifierSetMode(
+            values
+                .as_ref()
+                .and_then(|v| v.first())
+                .ok_or_else(|| anyhow!("value does not exist"))?
+                .to_lowercase()
+                .parse::<HumidifierMode>()?,
+        ),
+        "evap_humidifier" => {
+            let values:[String; 2] = values
+                .ok_or_else(|| anyhow!("value does not exist"))?
+                .try_into()
+                .map_err(|_| anyhow!("invalid number of elements."))?;
+
+            let mode = values[0].to_lowercase().parse::<EvaporativeHumidifierMode>()?;
+            let target_humidity = HumidityValue::try_from(values[1].parse::<u8>()?)?;
+
+            Command::EvaporativeHumidifierSetMode(EvaporativeHumidifierValues{mode, target_humidity})
+        }
+        "child_lock" => Command::SetChildLock(
+            match values
+                .as_ref()
+                .and_then(|v| v.first())
+                .ok_or_else(|| anyhow!("value does not exist"))?
+                .to_lowercase()
+                .as_str()
+            {
+                "on" | "true" => true,
+                "off" | "false" => false,
+                other => return Err(anyhow!("invalid child lock value: {other}")),
+            },
+        ),
+        _ => parse_custom_command(command, values, customize, param_json)?,
+    };
+
+    if has_param_json && !matches!(command, Command::Custom { .. }) {
+        return Err(anyhow!("--param-json can only be used with custom commands"));
+    }
+
+    Ok(command)
+}
+
+fn parse_custom_command(
+    command: String,
+    values: Option<Vec<String>>,
+    customize: bool,
+    param_json: Option<String>,
+) -> Result<Command> {
+    Ok(Command::Custom {
+        command_type: if customize {"customize".into()} else {"command".into()},
+        command,
+        parameter: match (param_json, values) {
+            (Some(json), _) => serde_json::from_str(&json)
+                .map_err(|e| anyhow!("invalid JSON parameter: {e}"))?,
+            (None, Some(values)) => parse_parameter(&values.join(",")),
+            (None, None) => "default".into(),
+        },
+    })
+}
+
+fn parse_vacuum_command(command: VacuumCommands) -> Result<Command> {
+    let command = match command {
+        VacuumCommands::Start => Command::VacuumStart,
+        VacuumCommands::Stop => Command::VacuumStop,
+        VacuumCommands::Dock => Command::VacuumDock,
+        VacuumCommands::PowerLevel { level } => {
+            Command::VacuumPowerLevel(VacuumPowerLevelValue::try_from(level)?)
+        }
+        VacuumCommands::Clean { action, fan_level, water_level, times } => {
+            let action = action.to_lowercase().parse::<VacuumCleanAction>()?;
+            let fan_level = VacuumFanLevelValue::try_from(fan_level)?;
+            let water_level = VacuumWaterLevelValue::try_from(water_level)?;
+            let times = VacuumCleanTimesValue::try_from(times)?;
+
+            Command::VacuumStartClean(VacuumCleanValues{action, fan_level, water_level, times})
+        }
+    };
+
+    Ok(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn customize_skips_typed_commands() {
+        let command = parse_exec_command("timer".into(), None, true, None).unwrap();
+
+        match command {
+            Command::Custom { command_type, command, parameter } => {
+                assert_eq!(command_type, "customize");
+                assert_eq!(command, "timer");
+                assert_eq!(parameter, "default");
+            }
+            other => panic!("expected a custom command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn known_command_without_customize_is_typed() {
+        let command = parse_exec_command("timer".into(), None, false, None).unwrap();
+
+        assert!(matches!(command, Command::IrFan(IrFanAction::TIMER)));
+    }
+
+    #[test]
+    fn param_json_is_rejected_for_typed_commands() {
+        let result = parse_exec_command("on".into(), None, false, Some("{}".into()));
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,61 @@
+use crate::domain::errors::{ApiError, ValidationError};
+
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    Success = 0,
+    Failure = 1,
+    UsageError = 2,
+    ValidationError = 3,
+    AuthError = 4,
+    NetworkError = 5,
+    RateLimited = 6,
+    DeviceNotFound = 10,
+    DeviceOffline = 11,
+    HubOffline = 12,
+    DeviceBusy = 13,
+    CommandNotSupported = 14,
+    DeviceTypeError = 15,
+    ApiError = 16,
+}
+
+impl ExitCode {
+    pub fn from_error(err: &anyhow::Error) -> Self {
+        if let Some(e) = err.downcast_ref::<ApiError>() {
+            return Self::from(e);
+        }
+        if err.downcast_ref::<ValidationError>().is_some() {
+            return Self::ValidationError;
+        }
+        if err.downcast_ref::<clap::Error>().is_some() {
+            return Self::UsageError;
+        }
+        if let Some(e) = err.downcast_ref::<reqwest::Error>()
+            && (e.is_connect() || e.is_timeout() || e.is_request())
+        {
+            return Self::NetworkError;
+        }
+
+        Self::Failure
+    }
+
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+}
+
+impl From<&ApiError> for ExitCode {
+    fn from(err: &ApiError) -> Self {
+        match err {
+            ApiError::DeviceTypeError { .. } => Self::DeviceTypeError,
+            ApiError::DeviceNotFound { .. } => Self::DeviceNotFound,
+            ApiError::CommandNotSupported { .. } => Self::CommandNotSupported,
+            ApiError::DeviceOffline { .. } => Self::DeviceOffline,
+            ApiError::HubOffline { .. } => Self::HubOffline,
+            ApiError::DeviceBusy { .. } => Self::DeviceBusy,
+            ApiError::Unauthorized => Self::AuthError,
+            ApiError::RateLimited | ApiError::BudgetExhausted { .. } => Self::RateLimited,
+            ApiError::Http { .. } | ApiError::Unknown { .. } => Self::ApiError,
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    Json,
+    Jsonl,
+    Csv,
+    #[default]
+    Table,
+    Yaml,
+}
+
+impl OutputFormat {
+    pub fn render<T: Serialize>(self, rows: &[T]) -> Result<String> {
+        match self {
+            Self::Json => Ok(serde_json::to_string_pretty(rows)? + "\n"),
+            Self::Yaml => Ok(serde_yaml::to_string(rows)?),
+            Self::Jsonl => rows
+                .iter()
+                .map(|v| Ok(serde_json::to_string(v)? + "\n"))
+                .collect(),
+            Self::Csv => {
+                let (headers, records) = to_records(rows)?;
+                render_csv(&headers, &records)
+            }
+            Self::Table => {
+                let (headers, records) = to_records(rows)?;
+                Ok(render_table(&headers, &records))
+            }
+        }
+    }
+
+    pub fn render_one<T: Serialize>(self, item: &T) -> Result<String> {
+        match self {
+            Self::Json => Ok(serde_json::to_string_pretty(item)? + "\n"),
+            Self::Yaml => Ok(serde_yaml::to_string(item)?),
+            _ => self.render(std::slice::from_ref(item)),
+        }
+    }
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(v) => v.clone(),
+        Value::Array(v) => v.iter().map(cell).collect::<Vec<_>>().join(","),
+        v => v.to_string(),
+    }
+}
+
+fn to_records<T: Serialize>(rows: &[T]) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+    let rows = rows
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let headers: Vec<String> = match rows.first() {
+        Some(Value::Object(map)) => map.keys().cloned().collect(),
+        _ => vec!["value".to_string()],
+    };
+
+    let records = rows
+        .iter()
+        .map(|row| match row {
+            Value::Object(map) => headers
+                .iter()
+                .map(|key| map.get(key).map(cell).unwrap_or_default())
+                .collect(),
+            v => vec![cell(v)],
+        })
+        .collect();
+
+    Ok((headers, records))
+}
+
+fn render_csv(headers: &[String], records: &[Vec<String>]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(headers)?;
+    for record in records {
+        writer.write_record(record)?;
+    }
+
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+fn render_table(headers: &[String], records: &[Vec<String>]) -> String {
+    if records.is_empty() {
+        return String::new();
+    }
+
+    let headers: Vec<String> = headers.iter().map(|v| v.to_uppercase()).collect();
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| {
+            records
+                .iter()
+                .map(|v| v[i].chars().count())
+                .chain([header.chars().count()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    std::iter::once(&headers)
+        .chain(records)
+        .map(|row| {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(value, width)| format!("{value:<width$}"))
+                .collect::<Vec<_>>()
+                .join("  ");
+            line.trim_end().to_string() + "\n"
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Row {
+        device_id: &'static str,
+        device_name: &'static str,
+        is_infrared: bool,
+        aliases: Vec<&'static str>,
+    }
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row { device_id: "A1", device_name: "Living Light", is_infrared: false, aliases: vec!["light", "lamp"] },
+            Row { device_id: "02-TV", device_name: "TV", is_infrared: true, aliases: vec![] },
+        ]
+    }
+
+    #[test]
+    fn table_is_aligned_in_field_order() {
+        let table = OutputFormat::Table.render(&rows()).unwrap();
+
+        assert_eq!(
+            table,
+            "DEVICE_ID  DEVICE_NAME   IS_INFRARED  ALIASES\n\
+             A1         Living Light  false        light,lamp\n\
+             02-TV      TV            true\n"
+        );
+    }
+
+    #[test]
+    fn csv_quotes_values_with_commas() {
+        let csv = OutputFormat::Csv.render(&rows()).unwrap();
+
+        assert_eq!(
+            csv,
+            "device_id,device_name,is_infrared,aliases\n\
+             A1,Living Light,false,\"light,lamp\"\n\
+             02-TV,TV,true,\n"
+        );
+    }
+
+    #[test]
+    fn jsonl_writes_one_object_per_line() {
+        let jsonl = OutputFormat::Jsonl.render(&rows()).unwrap();
+        let lines: Vec<&str> = jsonl.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[1],
+            r#"{"device_id":"02-TV","device_name":"TV","is_infrared":true,"aliases":[]}"#
+        );
+    }
+
+    #[test]
+    fn single_item_is_rendered_as_object() {
+        let row = &rows()[0];
+
+        assert!(OutputFormat::Json.render_one(row).unwrap().starts_with('{'));
+        assert!(OutputFormat::Yaml.render_one(row).unwrap().starts_with("device_id: A1"));
+    }
+}
@@ -0,0 +1,387 @@
+use std::path::PathBuf;
+use std::process::Output;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use serde_json::json;
+use tokio::process::Command;
+
+use switchbot_cli_tool::infrastructure::server::{
+    MockApiHandle, MockApiServer, MockFailure, MockFixtures,
+};
+
+const TOKEN: &str = "test-token";
+const SECRET: &str = "test-secret";
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/mock_api.json");
+
+static NEXT_DIR: AtomicU32 = AtomicU32::new(0);
+
+struct TestEnv {
+    host: String,
+    dir: PathBuf,
+    api: MockApiHandle,
+}
+
+impl TestEnv {
+    async fn start() -> Self {
+        let fixtures = MockFixtures::load(FIXTURES).unwrap();
+        let server = MockApiServer::bind("127.0.0.1:0", TOKEN, SECRET, fixtures)
+            .await
+            .unwrap();
+        let host = format!("http://{}", server.local_addr().unwrap());
+        let api = server.handle();
+        tokio::spawn(server.run());
+
+        let dir = std::env::temp_dir().join(format!(
+            "switchbot-cli-test-{}-{}",
+            std::process::id(),
+            NEXT_DIR.fetch_add(1, Ordering::SeqCst)
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("device_aliases.json"), r#"{"tv":"02-202301011200-12345678"}"#)
+            .unwrap();
+
+        Self { host, dir, api }
+    }
+
+    async fn run(&self, args: &[&str]) -> Output {
+        self.run_with_env(args, &[]).await
+    }
+
+    async fn run_with_env(&self, args: &[&str], env: &[(&str, &str)]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_switchbot-cli-tool"))
+            .args(args)
+            .current_dir(&self.dir)
+            .env_remove("RUST_LOG")
+            .env("SWITCHBOT_API_HOST", &self.host)
+            .env("SWITCHBOT_TOKEN", TOKEN)
+            .env("SWITCHBOT_SECRET", SECRET)
+            .env("SWITCHBOT_RETRY_BASE_DELAY_MS", "10")
+            .envs(env.iter().copied())
+            .output()
+            .await
+            .unwrap()
+    }
+}
+
+impl Drop for TestEnv {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[tokio::test]
+async fn list_prints_devices_and_remotes() {
+    let env = TestEnv::start().await;
+
+    let output = env.run(&["list"]).await;
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).contains("Living Light"));
+    assert!(stdout(&output).contains("Desk Bot"));
+    assert!(stdout(&output).contains("Bedroom TV"));
+    assert!(env.dir.join("output/devices.json").exists());
+}
+
+#[tokio::test]
+async fn list_with_wrong_secret_is_rejected() {
+    let env = TestEnv::start().await;
+
+    let output = env.run_with_env(&["list"], &[("SWITCHBOT_SECRET", "wrong-secret")]).await;
+
+    assert_eq!(output.status.code(), Some(4));
+}
+
+#[tokio::test]
+async fn exec_sends_signed_command() {
+    let env = TestEnv::start().await;
+
+    let output = env
+        .run(&["exec", "--device", "A1B2C3D4E5F6", "--command", "brightness", "--values", "40"])
+        .await;
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        env.api.commands("A1B2C3D4E5F6"),
+        vec![json!({ "commandType": "command", "command": "setBrightness", "parameter": 40 })]
+    );
+}
+
+#[tokio::test]
+async fn exec_resolves_alias_and_repeats_ir_command() {
+    let env = TestEnv::start().await;
+
+    let output = env
+        .run(&["exec", "-d", "tv", "-c", "volume_up", "--repeat", "3", "--interval-ms", "0"])
+        .await;
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(env.api.commands("02-202301011200-12345678").len(), 3);
+}
+
+#[tokio::test]
+async fn exec_rejects_unsupported_command_without_sending() {
+    let env = TestEnv::start().await;
+
+    let output = env.run(&["exec", "-d", "B1B2C3D4E5F6", "-c", "brightness", "-v", "40"]).await;
+
+    assert_eq!(output.status.code(), Some(3));
+    assert!(env.api.commands("B1B2C3D4E5F6").is_empty());
+}
+
+#[tokio::test]
+async fn exec_maps_api_status_code_to_exit_code() {
+    let env = TestEnv::start().await;
+    env.api.fail("/devices/A1B2C3D4E5F6/commands", MockFailure::StatusCode(161), 1);
+
+    let output = env.run(&["exec", "-d", "A1B2C3D4E5F6", "-c", "off"]).await;
+
+    assert_eq!(output.status.code(), Some(11));
+}
+
+#[tokio::test]
+async fn exec_on_unknown_device_exits_with_device_not_found() {
+    let env = TestEnv::start().await;
+
+    let output = env.run(&["exec", "-d", "FFFFFFFFFFFF", "-c", "on"]).await;
+
+    assert_eq!(output.status.code(), Some(10));
+    assert!(env.api.commands("FFFFFFFFFFFF").is_empty());
+}
+
+#[tokio::test]
+async fn exec_does_not_retry_commands_on_server_errors() {
+    let env = TestEnv::start().await;
+    env.api.fail("/devices/A1B2C3D4E5F6/commands", MockFailure::Http(503), 1);
+
+    let output = env.run(&["exec", "-d", "A1B2C3D4E5F6", "-c", "on"]).await;
+
+    assert_eq!(output.status.code(), Some(16));
+    assert_eq!(env.api.commands("A1B2C3D4E5F6").len(), 1);
+}
+
+#[tokio::test]
+async fn exec_retries_busy_device() {
+    let env = TestEnv::start().await;
+    env.api.fail("/devices/A1B2C3D4E5F6/commands", MockFailure::StatusCode(190), 1);
+
+    let output = env.run(&["exec", "-d", "A1B2C3D4E5F6", "-c", "on"]).await;
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(env.api.commands("A1B2C3D4E5F6").len(), 2);
+}
+
+#[tokio::test]
+async fn list_retries_server_errors() {
+    let env = TestEnv::start().await;
+    env.api.fail("/devices", MockFailure::Http(503), 2);
+
+    let output = env.run(&["list"]).await;
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(env.api.requests().iter().filter(|v| v.path == "/devices").count(), 3);
+}
+
+#[tokio::test]
+async fn list_gives_up_after_max_attempts() {
+    let env = TestEnv::start().await;
+    env.api.fail("/devices", MockFailure::Http(500), 5);
+
+    let output = env.run(&["list"]).await;
+
+    assert_eq!(output.status.code(), Some(16));
+    assert_eq!(env.api.requests().iter().filter(|v| v.path == "/devices").count(), 3);
+}
+
+#[tokio::test]
+async fn exec_checks_bot_mode_before_switching() {
+    let env = TestEnv::start().await;
+    env.api.set_status(
+        "B1B2C3D4E5F6",
+        json!({ "deviceId": "B1B2C3D4E5F6", "deviceType": "Bot", "deviceMode": "pressMode" }),
+    );
+
+    let output = env.run(&["exec", "-d", "B1B2C3D4E5F6", "-c", "on"]).await;
+
+    assert_eq!(output.status.code(), Some(3));
+    assert!(env.api.commands("B1B2C3D4E5F6").is_empty());
+}
+
+#[tokio::test]
+async fn invalid_http_settings_fail_before_any_request() {
+    let env = TestEnv::start().await;
+
+    let output = env
+        .run_with_env(&["list"], &[("SWITCHBOT_CA_BUNDLE", "missing-ca.pem")])
+        .await;
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to read CA bundle: missing-ca.pem"));
+    assert!(env.api.requests().is_empty());
+}
+
+#[tokio::test]
+async fn concurrent_runs_count_every_call() {
+    let env = TestEnv::start().await;
+
+    let outputs = tokio::join!(
+        env.run(&["list"]),
+        env.run(&["list"]),
+        env.run(&["list"]),
+        env.run(&["list"]),
+    );
+
+    for output in [outputs.0, outputs.1, outputs.2, outputs.3] {
+        assert_eq!(output.status.code(), Some(0));
+    }
+    let quota: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(env.dir.join("output/quota.json")).unwrap()).unwrap();
+    let counts: Vec<_> = quota.as_object().unwrap().values().map(|v| v["count"].clone()).collect();
+    assert_eq!(counts, vec![json!(4)]);
+}
+
+#[tokio::test]
+async fn corrupt_quota_file_is_kept_and_refused() {
+    let env = TestEnv::start().await;
+    let quota = env.dir.join("output/quota.json");
+    std::fs::create_dir_all(quota.parent().unwrap()).unwrap();
+    std::fs::write(&quota, "{not json").unwrap();
+
+    let output = env.run(&["list"]).await;
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("quota.json"));
+    assert_eq!(std::fs::read_to_string(&quota).unwrap(), "{not json");
+    assert!(env.api.requests().is_empty());
+}
+
+#[tokio::test]
+async fn replay_serves_recorded_responses_offline() {
+    let env = TestEnv::start().await;
+    let offline = [("SWITCHBOT_API_HOST", "http://127.0.0.1:9")];
+
+    let recorded = env.run(&["--record", "list", "list"]).await;
+    env.run(&["--record", "exec", "exec", "-d", "A1B2C3D4E5F6", "-c", "on"]).await;
+    let requests = env.api.requests().len();
+
+    let replayed = env.run_with_env(&["--replay", "list", "list"], &offline).await;
+    let exec = env
+        .run_with_env(&["--replay", "exec", "exec", "-d", "A1B2C3D4E5F6", "-c", "on"], &offline)
+        .await;
+
+    assert_eq!(replayed.status.code(), Some(0));
+    assert_eq!(stdout(&replayed), stdout(&recorded));
+    assert_eq!(exec.status.code(), Some(0));
+    assert_eq!(env.api.requests().len(), requests);
+}
+
+#[tokio::test]
+async fn cassette_redacts_auth_headers() {
+    let env = TestEnv::start().await;
+
+    env.run(&["--record", "cassette", "list"]).await;
+
+    let cassette = std::fs::read_to_string(env.dir.join("cassette/cassette.json")).unwrap();
+    assert!(cassette.contains("/devices"));
+    assert!(!cassette.contains(TOKEN));
+    assert!(cassette.contains("<redacted>"));
+}
+
+#[tokio::test]
+async fn replay_fails_on_unmatched_request() {
+    let env = TestEnv::start().await;
+    env.run(&["--record", "cassette", "list"]).await;
+
+    let output = env
+        .run(&["--replay", "cassette", "exec", "-d", "A1B2C3D4E5F6", "-c", "off"])
+        .await;
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No recorded response"));
+    assert!(env.api.commands("A1B2C3D4E5F6").is_empty());
+}
+
+#[tokio::test]
+async fn dry_run_prints_command_without_sending() {
+    let env = TestEnv::start().await;
+
+    let output = env
+        .run(&["exec", "-d", "A1B2C3D4E5F6", "-c", "brightness", "-v", "40", "--dry-run"])
+        .await;
+
+    let stdout = stdout(&output);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout.contains(&format!("POST {}/devices/A1B2C3D4E5F6/commands", env.host)));
+    assert!(stdout.contains(r#""command": "setBrightness""#));
+    assert!(stdout.contains("sign: <redacted>"));
+    assert!(!stdout.contains(TOKEN));
+    assert!(env.api.requests().is_empty());
+}
+
+#[tokio::test]
+async fn dry_run_skips_bot_lookups_without_network_access() {
+    let env = TestEnv::start().await;
+
+    let output = env.run(&["--dry-run", "exec", "-d", "B1B2C3D4E5F6", "-c", "on"]).await;
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).contains("/devices/B1B2C3D4E5F6/commands"));
+    assert!(env.api.requests().is_empty());
+}
+
+#[tokio::test]
+async fn dry_run_prints_scene_execution_without_sending() {
+    let env = TestEnv::start().await;
+
+    let output = env
+        .run(&["--dry-run", "scenes", "run", "T01-202301011200-00000001"])
+        .await;
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).contains("/scenes/T01-202301011200-00000001/execute"));
+    assert!(env.api.requests().is_empty());
+}
+
+#[tokio::test]
+async fn dry_run_does_not_request_read_only_commands() {
+    let env = TestEnv::start().await;
+
+    for args in [
+        &["--dry-run", "list"][..],
+        &["--dry-run", "status", "-d", "A1B2C3D4E5F6"],
+        &["--dry-run", "scenes", "list"],
+        &["--dry-run", "webhook", "query"],
+    ] {
+        let output = env.run(args).await;
+
+        assert_eq!(output.status.code(), Some(0));
+        assert!(stdout(&output).contains("read-only commands are not sent"));
+    }
+    assert!(env.api.requests().is_empty());
+    assert!(!env.dir.join("output/devices.json").exists());
+}
+
+#[tokio::test]
+async fn list_renders_json_with_aliases() {
+    let env = TestEnv::start().await;
+
+    let output = env.run(&["list", "--output", "json"]).await;
+
+    let devices: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(devices.as_array().unwrap().len(), 3);
+    assert_eq!(
+        devices[2],
+        json!({
+            "device_id": "02-202301011200-12345678",
+            "device_name": "Bedroom TV",
+            "device_type": "TV",
+            "is_infrared": true,
+            "hub_device_id": "000000000000",
+            "aliases": ["tv"],
+        })
+    );
+}